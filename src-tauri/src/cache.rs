@@ -0,0 +1,65 @@
+// 文件路径: src-tauri/src/cache.rs
+//
+// 最近一次 fetch_all_contests 的结果。提醒调度等后台任务从这里读取比赛列表，
+// 并持久化到应用数据目录，重启后在前端刷新之前也有数据可用。
+// 同时记住前端最近一次传入的抓取参数，托盘等后端发起的刷新沿用同一组参数。
+
+use crate::models::{Contest, ContestFetchOptions};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+const CACHE_FILE: &str = "contests_cache.json";
+
+static STORAGE_DIR: OnceLock<PathBuf> = OnceLock::new();
+static CONTESTS: OnceLock<Mutex<Vec<Contest>>> = OnceLock::new();
+static FETCH_OPTIONS: Mutex<Option<ContestFetchOptions>> = Mutex::new(None);
+
+/// 设置缓存目录 (应用数据目录)，只在启动时调用一次
+pub fn set_storage_dir(dir: PathBuf) {
+    let _ = STORAGE_DIR.set(dir);
+}
+
+fn cache_path() -> Option<PathBuf> {
+    STORAGE_DIR.get().map(|dir| dir.join(CACHE_FILE))
+}
+
+// 首次访问时从磁盘加载
+fn store() -> &'static Mutex<Vec<Contest>> {
+    CONTESTS.get_or_init(|| {
+        let contests = cache_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Mutex::new(contests)
+    })
+}
+
+/// 当前缓存的比赛列表 (按开始时间排序)
+pub fn contests() -> Vec<Contest> {
+    store().lock().unwrap().clone()
+}
+
+/// 用最新一次抓取结果替换缓存并写盘
+pub fn store_contests(contests: &[Contest]) {
+    *store().lock().unwrap() = contests.to_vec();
+
+    // 持久化失败不影响本次结果
+    let Some(path) = cache_path() else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string(contests) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+/// 最近一次前端调用 fetch_all_contests 时的参数，尚未调用过时取默认值
+pub fn fetch_options() -> ContestFetchOptions {
+    FETCH_OPTIONS.lock().unwrap().clone().unwrap_or_default()
+}
+
+pub fn set_fetch_options(options: &ContestFetchOptions) {
+    *FETCH_OPTIONS.lock().unwrap() = Some(options.clone());
+}
@@ -0,0 +1,196 @@
+// 文件路径: src-tauri/src/changes.rs
+//
+// 比赛变更记录。每次刷新都与上一次持久化的列表 (cache.rs) 按比赛 id 比较，
+// 把改期 (移动了多久)、改名、取消记为变更事件并写入应用数据目录，
+// 前端据此标注 "已改期" 等信息，提醒模块据此按新时间重新安排提醒。
+// 比赛开始 KEEP_DAYS 天后其记录即被清理。
+
+use crate::models::{ContestChange, ContestChangeKind, ContestsUpdate};
+use crate::reminders;
+use chrono::{DateTime, Duration, Utc};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+const CHANGES_FILE: &str = "contest_changes.json";
+const KEEP_DAYS: i64 = 7;
+
+static STORAGE_DIR: OnceLock<PathBuf> = OnceLock::new();
+static CHANGES: OnceLock<Mutex<Vec<ContestChange>>> = OnceLock::new();
+
+/// 设置记录文件目录 (应用数据目录)，只在启动时调用一次
+pub fn set_storage_dir(dir: PathBuf) {
+    let _ = STORAGE_DIR.set(dir);
+}
+
+fn changes_path() -> Option<PathBuf> {
+    STORAGE_DIR.get().map(|dir| dir.join(CHANGES_FILE))
+}
+
+// 按发现时间升序保存，首次访问时从磁盘加载
+fn store() -> &'static Mutex<Vec<ContestChange>> {
+    CHANGES.get_or_init(|| {
+        let changes = changes_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Mutex::new(changes)
+    })
+}
+
+fn save(changes: &[ContestChange]) {
+    let Some(path) = changes_path() else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(changes) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+/// 全部变更记录，最新的在前
+pub fn list() -> Vec<ContestChange> {
+    let mut changes = store().lock().unwrap().clone();
+    changes.reverse();
+    changes
+}
+
+/// 把一次刷新的变化记为变更事件并持久化，返回本次新增的事件
+/// 只有结束时间变化 (时长调整) 的比赛不算改期
+pub fn record(update: &ContestsUpdate, now: DateTime<Utc>) -> Vec<ContestChange> {
+    let mut events = Vec::new();
+
+    for change in &update.rescheduled {
+        let contest = &change.contest;
+        if change.old_start_time == contest.start_time {
+            continue;
+        }
+        events.push(ContestChange {
+            contest_id: contest.id().to_string(),
+            platform: contest.platform.clone(),
+            name: contest.name.clone(),
+            kind: ContestChangeKind::Rescheduled,
+            detected_at: now,
+            start_time: contest.start_time,
+            old_start_time: Some(change.old_start_time),
+            shift_minutes: Some((contest.start_time - change.old_start_time).num_minutes()),
+            old_name: None,
+        });
+    }
+    for rename in &update.renamed {
+        let contest = &rename.contest;
+        events.push(ContestChange {
+            contest_id: contest.id().to_string(),
+            platform: contest.platform.clone(),
+            name: contest.name.clone(),
+            kind: ContestChangeKind::Renamed,
+            detected_at: now,
+            start_time: contest.start_time,
+            old_start_time: None,
+            shift_minutes: None,
+            old_name: Some(rename.old_name.clone()),
+        });
+    }
+    for contest in &update.cancelled {
+        events.push(ContestChange {
+            contest_id: contest.id().to_string(),
+            platform: contest.platform.clone(),
+            name: contest.name.clone(),
+            kind: ContestChangeKind::Cancelled,
+            detected_at: now,
+            start_time: contest.start_time,
+            old_start_time: None,
+            shift_minutes: None,
+            old_name: None,
+        });
+    }
+
+    if !events.is_empty() {
+        let mut changes = store().lock().unwrap();
+        changes.extend(events.iter().cloned());
+        changes.retain(|c| c.start_time > now - Duration::days(KEEP_DAYS));
+        save(&changes);
+    }
+    events
+}
+
+/// "推迟 2 小时" / "提前 30 分钟"
+pub fn format_shift(minutes: i64) -> String {
+    let text = reminders::format_remaining(Duration::minutes(minutes.abs()));
+    if minutes >= 0 {
+        format!("推迟 {}", text)
+    } else {
+        format!("提前 {}", text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_support::{self, now};
+    use crate::models::{Contest, ContestRename, ContestReschedule};
+
+    fn contest(id: &str, name: &str, hours: i64) -> Contest {
+        test_support::contest("Codeforces", id, name, hours)
+    }
+
+    #[test]
+    fn records_reschedule_rename_and_cancellation() {
+        let moved = contest("moved", "Round 1", 7);
+        let longer = contest("longer", "Round 2", 30);
+        let renamed = contest("renamed", "Round 3 (Div. 2)", 50);
+        let cancelled = contest("cancelled", "Round 4", 70);
+        let update = ContestsUpdate {
+            rescheduled: vec![
+                ContestReschedule {
+                    contest: moved.clone(),
+                    old_start_time: moved.start_time - Duration::hours(2),
+                    old_end_time: moved.end_time,
+                },
+                ContestReschedule {
+                    contest: longer.clone(),
+                    old_start_time: longer.start_time,
+                    old_end_time: None,
+                },
+            ],
+            renamed: vec![ContestRename {
+                contest: renamed.clone(),
+                old_name: "Round 3".to_string(),
+            }],
+            cancelled: vec![cancelled.clone()],
+            ..Default::default()
+        };
+
+        let events = record(&update, now());
+        // 只有结束时间变化的比赛不记为改期
+        assert_eq!(events.len(), 3);
+
+        assert_eq!(events[0].kind, ContestChangeKind::Rescheduled);
+        assert_eq!(events[0].contest_id, moved.id());
+        assert_eq!(events[0].shift_minutes, Some(120));
+        assert_eq!(
+            events[0].old_start_time,
+            Some(moved.start_time - Duration::hours(2))
+        );
+
+        assert_eq!(events[1].kind, ContestChangeKind::Renamed);
+        assert_eq!(events[1].contest_id, renamed.id());
+        assert_eq!(events[1].old_name.as_deref(), Some("Round 3"));
+
+        assert_eq!(events[2].kind, ContestChangeKind::Cancelled);
+        assert_eq!(events[2].contest_id, cancelled.id());
+        assert!(events.iter().all(|e| e.detected_at == now()));
+    }
+
+    #[test]
+    fn empty_update_records_nothing() {
+        assert!(record(&ContestsUpdate::default(), now()).is_empty());
+    }
+
+    #[test]
+    fn formats_shift_direction() {
+        assert_eq!(format_shift(120), "推迟 2 小时");
+        assert_eq!(format_shift(-30), "提前 30 分钟");
+    }
+}
@@ -1,60 +1,50 @@
-use crate::models::{Contest, UserStats};
-use anyhow::Result;
+use crate::models::{
+    CodeChefProfile, Contest, ContestChange, ContestFetchOptions, DaimayuanProfile, HduProfile,
+    LeetCodeDaily, LeetCodeProfile, LuoguProfile, NowCoderSession, RatingEvent, RefreshSettings,
+    ReminderSettings, ScraperError, UserStats, WebhookConfig, WebhookFailure,
+};
+use crate::session::SessionExpiredError;
+use tauri::Manager;
 
+mod cache;
+mod changes;
 mod models;
+mod refresh;
+mod reminders;
+mod session;
+mod time_parse;
+mod tray;
+mod webhooks;
 mod platforms {
+    pub mod acwing;
     pub mod atcoder;
+    pub mod clist;
+    pub mod codechef;
     pub mod codeforces;
+    pub mod custom;
     pub mod daimayuan;
     pub mod hdu;
     pub mod leetcode;
+    pub mod libreoj;
     pub mod luogu;
     pub mod nowcoder; // [已注册]
+    pub mod qoj;
+    pub mod topcoder;
+    pub mod uoj;
+    pub mod yukicoder;
 }
 
 #[tauri::command]
-async fn fetch_all_contests() -> Result<Vec<Contest>, String> {
-    // 并发执行所有平台的抓取任务
-    // 目前包含: CF, AtCoder, NowCoder, LeetCode, HDU, Luogu, Daimayuan (共7个)
-    let (cf_res, ac_res, nc_res, lc_res, hdu_res, lg_res, dmy_res) = tokio::join!(
-        platforms::codeforces::fetch_contests(),
-        platforms::atcoder::fetch_contests(),
-        platforms::nowcoder::fetch_contests(),
-        platforms::leetcode::fetch_contests(),
-        platforms::hdu::fetch_contests(),
-        platforms::luogu::fetch_contests(),
-        platforms::daimayuan::fetch_contests()
-    );
+async fn fetch_all_contests(
+    app: tauri::AppHandle,
+    options: Option<ContestFetchOptions>,
+) -> Result<Vec<Contest>, String> {
+    let options = options.unwrap_or_default();
+    // [新增] 记住参数，托盘的 "立即刷新" 沿用同一组设置
+    cache::set_fetch_options(&options);
 
-    let mut all_contests = Vec::new();
-
-    // 聚合结果，忽略单个平台的失败
-    if let Ok(c) = cf_res {
-        all_contests.extend(c);
-    }
-    if let Ok(c) = ac_res {
-        all_contests.extend(c);
-    }
-    if let Ok(c) = nc_res {
-        all_contests.extend(c);
-    }
-    if let Ok(c) = lc_res {
-        all_contests.extend(c);
-    }
-    if let Ok(c) = hdu_res {
-        all_contests.extend(c);
-    }
-    if let Ok(c) = lg_res {
-        all_contests.extend(c);
-    }
-    if let Ok(c) = dmy_res {
-        all_contests.extend(c);
-    }
-
-    // 统一按开始时间排序
-    all_contests.sort_by(|a, b| a.start_time.cmp(&b.start_time));
-
-    Ok(all_contests)
+    // [修改] 抓取与合并移至 refresh.rs，与后台自动刷新共用
+    Ok(refresh::refresh_all(&app, options).await)
 }
 
 #[tauri::command]
@@ -62,6 +52,7 @@ async fn fetch_user_stats(
     platform: String,
     handle: String,
     cookie: Option<String>,
+    region: Option<String>,
 ) -> Result<UserStats, String> {
     let max_retries = 3;
     let mut last_error = String::new();
@@ -77,14 +68,23 @@ async fn fetch_user_stats(
                 .map_err(|e| e.to_string()),
             "nowcoder" => {
                 let user_cookie = cookie.as_deref().unwrap_or("");
-                platforms::nowcoder::fetch_user_stats(&handle, user_cookie)
+                match platforms::nowcoder::fetch_user_stats(&handle, user_cookie).await {
+                    // Cookie 失效时重试无意义，直接返回
+                    Err(e) if e.is::<SessionExpiredError>() => return Err(e.to_string()),
+                    res => res.map_err(|e| e.to_string()),
+                }
+            }
+            "leetcode" => {
+                // [新增] region: "cn" (默认) / "com"
+                let lc_region = platforms::leetcode::Region::from_option(region.as_deref());
+                platforms::leetcode::fetch_user_stats(&handle, lc_region)
                     .await
                     .map_err(|e| e.to_string())
             }
-            "leetcode" => platforms::leetcode::fetch_user_stats(&handle)
+            "hdu" => platforms::hdu::fetch_user_stats(&handle)
                 .await
                 .map_err(|e| e.to_string()),
-            "luogu" => platforms::luogu::fetch_user_stats(&handle)
+            "luogu" => platforms::luogu::fetch_user_stats(&handle, cookie.as_deref())
                 .await
                 .map_err(|e| e.to_string()),
             "daimayuan" => {
@@ -93,6 +93,21 @@ async fn fetch_user_stats(
                     .await
                     .map_err(|e| e.to_string())
             }
+            "codechef" => platforms::codechef::fetch_user_stats(&handle)
+                .await
+                .map_err(|e| e.to_string()),
+            "yukicoder" => platforms::yukicoder::fetch_user_stats(&handle)
+                .await
+                .map_err(|e| e.to_string()),
+            "libreoj" | "loj" => platforms::libreoj::fetch_user_stats(&handle)
+                .await
+                .map_err(|e| e.to_string()),
+            "uoj" => platforms::uoj::fetch_user_stats(&handle)
+                .await
+                .map_err(|e| e.to_string()),
+            "qoj" => platforms::qoj::fetch_user_stats(&handle)
+                .await
+                .map_err(|e| e.to_string()),
             _ => Err(format!("Platform '{}' not supported yet", platform)),
         };
 
@@ -114,18 +129,204 @@ async fn fetch_user_stats(
     Err(last_error)
 }
 
+// [新增] 统一格式的 Rating 历史 (按时间升序)
+// cookie / region 的含义与 fetch_user_stats 一致
+#[tauri::command]
+async fn fetch_rating_history(
+    platform: String,
+    handle: String,
+    cookie: Option<String>,
+    region: Option<String>,
+) -> Result<Vec<RatingEvent>, String> {
+    let res = match platform.to_lowercase().as_str() {
+        "codeforces" => platforms::codeforces::fetch_rating_history(&handle).await,
+        "atcoder" => platforms::atcoder::fetch_rating_history(&handle).await,
+        "leetcode" => {
+            let lc_region = platforms::leetcode::Region::from_option(region.as_deref());
+            platforms::leetcode::fetch_rating_history(&handle, lc_region).await
+        }
+        "luogu" => platforms::luogu::fetch_rating_history(&handle, cookie.as_deref()).await,
+        "nowcoder" => platforms::nowcoder::fetch_rating_history(&handle, cookie.as_deref()).await,
+        "daimayuan" => platforms::daimayuan::fetch_rating_history(&handle).await,
+        _ => return Err(format!("Platform '{}' has no rating history", platform)),
+    };
+
+    res.map_err(|e| e.to_string())
+}
+
+// [新增] LeetCode 详细战绩 (难度拆分、周赛排名、Rating 历史)
+#[tauri::command]
+async fn fetch_leetcode_profile(
+    handle: String,
+    region: Option<String>,
+) -> Result<LeetCodeProfile, String> {
+    let lc_region = platforms::leetcode::Region::from_option(region.as_deref());
+    platforms::leetcode::fetch_profile(&handle, lc_region)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// [新增] LeetCode 每日一题 (国服 + 国际服) 及用户最近 AC
+#[tauri::command]
+async fn fetch_leetcode_daily(
+    handle: Option<String>,
+    region: Option<String>,
+) -> Result<LeetCodeDaily, String> {
+    let lc_region = platforms::leetcode::Region::from_option(region.as_deref());
+    platforms::leetcode::fetch_daily(handle.as_deref(), lc_region)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// [新增] 洛谷详细战绩 (可选携带登录 Cookie)
+#[tauri::command]
+async fn fetch_luogu_profile(uid: String, cookie: Option<String>) -> Result<LuoguProfile, String> {
+    platforms::luogu::fetch_profile(&uid, cookie.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// [新增] HDU 详细战绩 (含已通过题号)
+#[tauri::command]
+async fn fetch_hdu_profile(handle: String) -> Result<HduProfile, String> {
+    platforms::hdu::fetch_profile(&handle)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// [新增] 代码源详细战绩 (Rating 历史、已通过题目)
+#[tauri::command]
+async fn fetch_daimayuan_profile(handle: String) -> Result<DaimayuanProfile, String> {
+    platforms::daimayuan::fetch_profile(&handle)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// [新增] CodeChef 详细战绩 (星级、全球/国家排名)
+#[tauri::command]
+async fn fetch_codechef_profile(handle: String) -> Result<CodeChefProfile, String> {
+    platforms::codechef::fetch_profile(&handle)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// [新增] 检查 NowCoder Cookie 是否仍处于登录态
+#[tauri::command]
+async fn validate_nowcoder_cookie(cookie: String) -> Result<NowCoderSession, String> {
+    platforms::nowcoder::validate_cookie(&cookie)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// [新增] 比赛提醒设置 (订阅与提前量)
+#[tauri::command]
+fn get_reminder_settings() -> ReminderSettings {
+    reminders::settings()
+}
+
+#[tauri::command]
+fn set_reminder_settings(settings: ReminderSettings) -> Result<(), String> {
+    reminders::set_settings(settings).map_err(|e| e.to_string())
+}
+
+// [新增] 后台自动刷新设置 (刷新间隔与需要刷新战绩的账号)
+#[tauri::command]
+fn get_refresh_settings() -> RefreshSettings {
+    refresh::settings()
+}
+
+#[tauri::command]
+fn set_refresh_settings(settings: RefreshSettings) {
+    refresh::set_settings(settings);
+}
+
+// [新增] 比赛变更记录 (改期 / 改名 / 取消)，最新的在前
+#[tauri::command]
+fn get_contest_changes() -> Vec<ContestChange> {
+    changes::list()
+}
+
+// [新增] 向单个 Webhook 发送示例消息，检查地址与模板
+#[tauri::command]
+async fn test_webhook(hook: WebhookConfig) -> Result<(), String> {
+    webhooks::send_test(&hook).await.map_err(|e| e.to_string())
+}
+
+// [新增] 配置目录 scrapers/ 下无法加载的自定义爬虫定义
+#[tauri::command]
+fn get_scraper_errors() -> Vec<ScraperError> {
+    platforms::custom::definition_errors()
+}
+
+// [新增] 各 Webhook 最近一次自动推送失败的原因
+#[tauri::command]
+fn get_webhook_failures() -> Vec<WebhookFailure> {
+    webhooks::failures()
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
+        // [新增] 比赛提醒使用的桌面通知
+        .plugin(tauri_plugin_notification::init())
         // [新增] 注册更新插件
         // 注意：这会自动读取 tauri.conf.json 中的 updater 配置
         .plugin(tauri_plugin_updater::Builder::new().build())
+        .setup(|app| {
+            // [新增] 反爬会话的 Cookie 持久化目录
+            if let Ok(dir) = app.path().app_data_dir() {
+                session::set_storage_dir(dir.clone());
+                // [新增] 比赛缓存与提醒状态同样放在数据目录
+                cache::set_storage_dir(dir.clone());
+                reminders::set_storage_dir(dir.clone());
+                refresh::set_storage_dir(dir.clone());
+                changes::set_storage_dir(dir);
+            }
+            // [新增] 自定义爬虫定义位于配置目录的 scrapers/ 下
+            if let Ok(dir) = app.path().app_config_dir() {
+                platforms::custom::set_config_dir(dir);
+            }
+            // [新增] 后台提醒调度
+            reminders::start(app.handle().clone());
+            // [新增] 后台自动刷新比赛列表与账号战绩
+            refresh::start(app.handle().clone());
+            // [新增] 系统托盘 (最近比赛 + 快捷操作)，创建失败时不影响主窗口
+            let _ = tray::init(app.handle());
+            Ok(())
+        })
+        // [新增] 关闭主窗口时隐藏到托盘，后台提醒继续运行；退出请使用托盘菜单
+        // 没有托盘时无法再打开窗口，按原样关闭
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                if !tray::is_available(window.app_handle()) {
+                    return;
+                }
+                let _ = window.hide();
+                api.prevent_close();
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             fetch_all_contests,
-            fetch_user_stats
+            fetch_user_stats,
+            fetch_rating_history,
+            fetch_leetcode_profile,
+            fetch_leetcode_daily,
+            fetch_luogu_profile,
+            fetch_hdu_profile,
+            fetch_daimayuan_profile,
+            fetch_codechef_profile,
+            validate_nowcoder_cookie,
+            get_reminder_settings,
+            set_reminder_settings,
+            get_refresh_settings,
+            set_refresh_settings,
+            get_contest_changes,
+            test_webhook,
+            get_webhook_failures,
+            get_scraper_errors
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
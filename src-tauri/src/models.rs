@@ -1,17 +1,117 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 // 现有的 Contest 结构体
-#[derive(Debug, Serialize, Deserialize, Clone)]
+// 新增字段均为可选，未提供该信息的平台使用 ..Default::default() 留空
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct Contest {
     pub name: String,
     pub start_time: DateTime<Utc>,
     pub url: String,
     pub platform: String,
+    // [新增] 结束时间 (部分平台只给出时长，由开始时间推算)
+    #[serde(default)]
+    pub end_time: Option<DateTime<Utc>>,
+    // [新增] Rated 范围，None 表示不计分或平台未提供
+    #[serde(default)]
+    pub rated_range: Option<RatedRange>,
+    // [新增] 赛制 (e.g. "OI" / "IOI" / "ACM")
+    #[serde(default)]
+    pub format: Option<String>,
+    // [新增] 比赛分类 (e.g. 洛谷 "官方比赛" / "个人公开赛")
+    #[serde(default)]
+    pub category: Option<String>,
+    // [新增] 主办方 (用户或团队名)
+    #[serde(default)]
+    pub host: Option<String>,
+    // [新增] 参赛权限 (e.g. HDU "Public" / "Private" / "Register")
+    #[serde(default)]
+    pub access: Option<String>,
+}
+
+impl Contest {
+    // [新增] 比赛 id：各平台的比赛链接互不相同，刷新前后比较、提醒订阅均以此为键
+    pub fn id(&self) -> &str {
+        &self.url
+    }
+}
+
+// [新增] Rated 范围 (闭区间)，上下界为 None 表示该侧不设限
+// 例: AtCoder "- 1999" => { min: None, max: Some(1999) }, "All" => { min: None, max: None }
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RatedRange {
+    pub min: Option<u32>,
+    pub max: Option<u32>,
+}
+
+// [新增] fetch_all_contests 的可选参数，前端不传时全部取默认值
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ContestFetchOptions {
+    pub luogu: LuoguContestOptions,
+    pub hdu: HduContestOptions,
+    pub nowcoder: NowCoderContestOptions,
+    pub clist: ClistOptions,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct LuoguContestOptions {
+    pub window_days: i64, // 只返回该天数内开始的比赛
+    pub official_only: bool,
+    pub rated_only: bool,
+}
+
+impl Default for LuoguContestOptions {
+    fn default() -> Self {
+        Self {
+            window_days: 14,
+            official_only: false,
+            rated_only: false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct HduContestOptions {
+    pub limit: Option<usize>, // 只保留最近的 N 场，None 表示不限制
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct NowCoderContestOptions {
+    pub max_pages: u32, // 每个分类最多抓取的页数
+}
+
+impl Default for NowCoderContestOptions {
+    fn default() -> Self {
+        Self { max_pages: 2 }
+    }
+}
+
+// [新增] clist.by 聚合源，username 与 api_key 都填写后才启用
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ClistOptions {
+    pub username: Option<String>,
+    pub api_key: Option<String>,
+    pub window_days: i64, // 只取该天数内开始的比赛
+}
+
+impl Default for ClistOptions {
+    fn default() -> Self {
+        Self {
+            username: None,
+            api_key: None,
+            window_days: 30,
+        }
+    }
 }
 
 // [新增] 用户刷题统计结构体
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct UserStats {
     pub platform: String,  // 平台 (e.g., "Codeforces")
     pub handle: String,    // 用户名/ID
@@ -20,3 +120,376 @@ pub struct UserStats {
     pub rank: Option<String>,
     pub rating: Option<u32>,
 }
+
+// [新增] 按难度拆分的 AC 题数
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct DifficultyBreakdown {
+    pub easy: u32,
+    pub medium: u32,
+    pub hard: u32,
+    pub total: u32,
+}
+
+// [新增] LeetCode 周赛排名信息
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LeetCodeContestRanking {
+    pub rating: f64,
+    pub attended_contests_count: u32,
+    pub global_ranking: Option<u32>,
+    pub top_percentage: Option<f64>, // 全球百分位, e.g. 5.3 表示 Top 5.3%
+}
+
+// [新增] LeetCode 单场周赛的 Rating 记录
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LeetCodeRatingEntry {
+    pub contest_name: String,
+    pub contest_slug: String,
+    pub start_time: DateTime<Utc>,
+    pub rating: f64, // 该场结束后的 Rating
+    pub ranking: u32,
+}
+
+// [新增] LeetCode 详细战绩
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LeetCodeProfile {
+    pub handle: String,
+    pub solved: DifficultyBreakdown,
+    // 未参加过周赛的用户为 None
+    pub contest_ranking: Option<LeetCodeContestRanking>,
+    // 仅包含实际参加的场次，按时间升序
+    pub rating_history: Vec<LeetCodeRatingEntry>,
+}
+
+// [新增] LeetCode 每日一题
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DailyQuestion {
+    pub region: String, // "cn" / "com"
+    pub date: String,   // 站点当日日期, e.g. "2024-05-01"
+    pub frontend_id: String,
+    pub title: String,
+    pub difficulty: String, // "Easy" / "Medium" / "Hard"
+    pub url: String,
+    // 未配置 handle 或账号不在该站点时为 None
+    pub solved: Option<bool>,
+}
+
+// [新增] 最近通过的提交
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecentAcSubmission {
+    pub title: String,
+    pub title_slug: String,
+    pub url: String,
+    pub time: DateTime<Utc>,
+}
+
+// [新增] 每日一题面板数据
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LeetCodeDaily {
+    pub questions: Vec<DailyQuestion>,
+    pub recent_ac: Vec<RecentAcSubmission>,
+}
+
+// [新增] 洛谷详细战绩
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LuoguProfile {
+    pub uid: String,
+    pub name: Option<String>,
+    pub solved_count: u32,
+    pub rating: Option<u32>,
+    pub ranking: Option<u32>,
+    // 通过的题号列表；用户隐藏练习记录且未以本人身份登录时为 None
+    pub passed_problems: Option<Vec<String>>,
+    // 按难度分级的通过题目，与 passed_problems 同时可用；固定 8 档，按难度升序
+    pub difficulty_distribution: Option<Vec<LuoguDifficultyBucket>>,
+    // Cookie 中的 _uid 与查询对象一致，即以本人身份访问
+    pub authenticated: bool,
+}
+
+// [新增] 洛谷单个难度档的通过情况
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LuoguDifficultyBucket {
+    pub level: u8,     // 0 (暂无评定) ~ 7 (NOI/NOI+/CTSC)
+    pub name: String,  // e.g. "普及+/提高"
+    pub color: String, // 洛谷站内对应颜色, e.g. "#52c41a"
+    pub count: u32,
+    pub problems: Vec<String>,
+}
+
+// [新增] HDU 详细战绩
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HduProfile {
+    pub handle: String,
+    pub rank: Option<u32>,
+    pub solved_count: u32,
+    pub submitted_count: u32, // 提交过的题目数
+    pub submissions: u32,     // 总提交次数
+    pub accepted: u32,        // AC 次数 (未去重)
+    pub solved_problems: Vec<u32>,
+}
+
+// [新增] NowCoder Cookie 会话状态
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NowCoderSession {
+    pub logged_in: bool,
+    pub uid: Option<String>,
+    // 服务端刷新登录态时下发的过期时间，未下发时为 None
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+// [新增] 代码源单场比赛的 Rating 变化
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DaimayuanRatingEntry {
+    pub contest_name: String,
+    pub contest_url: Option<String>,
+    pub rank: Option<u32>,
+    pub old_rating: Option<u32>,
+    pub new_rating: u32,
+    pub time: Option<DateTime<Utc>>,
+}
+
+// [新增] 代码源详细战绩
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DaimayuanProfile {
+    pub handle: String,
+    pub rating: Option<u32>,
+    pub solved_count: u32,
+    // 按时间升序
+    pub rating_history: Vec<DaimayuanRatingEntry>,
+    // 已通过的题号 (来自评测记录，去重)
+    pub solved_problems: Vec<String>,
+}
+
+// [新增] 统一的单场 Rating 变化记录 (仿 Codeforces user.rating)
+// 各平台的 Rating 历史都转换为该结构，按时间升序返回
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RatingEvent {
+    pub platform: String,
+    pub contest_id: String, // 平台内比赛标识 (id 或 slug)
+    pub contest_name: String,
+    // 代码源部分记录页面上不显示时间
+    pub time: Option<DateTime<Utc>>,
+    pub old_rating: Option<i32>, // 首场或平台未提供时为 None
+    pub new_rating: i32,
+    pub rank: Option<u32>,
+    pub performance: Option<i32>, // 目前只有 AtCoder 提供
+}
+
+// [新增] CodeChef 详细战绩
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CodeChefProfile {
+    pub handle: String,
+    pub rating: Option<u32>,
+    pub stars: Option<u32>, // 1★ ~ 7★
+    pub global_rank: Option<u32>,
+    pub country_rank: Option<u32>,
+    pub country: Option<String>,
+    pub solved_count: u32,
+}
+
+// [新增] 比赛提醒设置，订阅单场比赛 (按链接) 或整个平台
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ReminderSettings {
+    pub enabled: bool,
+    pub lead_minutes: Vec<i64>, // 提前量 (分钟)
+    pub platforms: Vec<String>,
+    pub contests: Vec<String>, // 比赛链接
+    // [新增] 提醒同时推送到的 Webhook
+    pub webhooks: Vec<WebhookConfig>,
+    // [新增] 每日赛程推送时间 (本地时间 "HH:MM")，None 表示不推送
+    pub daily_digest: Option<String>,
+}
+
+impl Default for ReminderSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            lead_minutes: vec![24 * 60, 60, 10],
+            platforms: Vec::new(),
+            contests: Vec::new(),
+            webhooks: Vec::new(),
+            daily_digest: None,
+        }
+    }
+}
+
+// [新增] Webhook 类型，决定请求体格式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookKind {
+    #[default]
+    Generic, // {"text": ..., "contests": [...]}
+    Discord,
+    Slack,
+    Feishu, // 飞书 / Lark 自定义机器人
+    DingTalk,
+    Telegram, // url 为 https://api.telegram.org/bot<token>/sendMessage
+}
+
+// [新增] 单个 Webhook 配置
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WebhookConfig {
+    pub name: String,
+    #[serde(default)]
+    pub kind: WebhookKind,
+    pub url: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    // Telegram 必填
+    #[serde(default)]
+    pub chat_id: Option<String>,
+    // 消息模板，可用占位符见 webhooks.rs；None 使用默认模板
+    #[serde(default)]
+    pub template: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// [新增] 无法加载的自定义爬虫定义文件
+#[derive(Debug, Serialize, Clone)]
+pub struct ScraperError {
+    pub file: String, // scrapers/ 下的文件名
+    pub error: String,
+}
+
+// [新增] Webhook 最近一次自动推送 (提醒 / 每日赛程) 失败的记录，推送成功后清除
+#[derive(Debug, Serialize, Clone)]
+pub struct WebhookFailure {
+    pub name: String,
+    pub url: String, // 与 WebhookConfig.url 对应
+    pub error: String,
+    pub time: DateTime<Utc>,
+}
+
+// [新增] 后台自动刷新设置
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct RefreshSettings {
+    pub enabled: bool,
+    pub interval_minutes: u64, // 比赛列表的默认刷新间隔
+    // 按来源覆盖刷新间隔 (分钟)，键为平台名或 "custom" / "clist"，0 表示不自动刷新
+    pub platform_intervals: BTreeMap<String, u64>,
+    pub stats_interval_minutes: u64, // 账号战绩刷新间隔，0 表示不刷新
+    // 需要后台刷新战绩的账号，由前端按已保存的 handle 同步
+    pub accounts: Vec<RefreshAccount>,
+}
+
+impl Default for RefreshSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_minutes: 30,
+            platform_intervals: BTreeMap::new(),
+            stats_interval_minutes: 60,
+            accounts: Vec::new(),
+        }
+    }
+}
+
+// [新增] 后台刷新战绩的账号，参数含义与 fetch_user_stats 一致
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RefreshAccount {
+    pub platform: String, // 前端的平台键, e.g. "codeforces"
+    pub handle: String,
+    #[serde(default)]
+    pub cookie: Option<String>,
+    #[serde(default)]
+    pub region: Option<String>,
+}
+
+// [新增] contests-updated 事件：完整列表及与上一次相比的变化 (以比赛 id 为键)
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ContestsUpdate {
+    pub contests: Vec<Contest>,
+    pub added: Vec<Contest>,
+    pub rescheduled: Vec<ContestReschedule>,
+    pub renamed: Vec<ContestRename>,
+    pub cancelled: Vec<Contest>, // 尚未开始就从列表中消失的比赛
+    // 本次记录的变更事件 (见 changes.rs)
+    pub changes: Vec<ContestChange>,
+}
+
+// [新增] 改期的比赛 (contest 为新数据)
+#[derive(Debug, Serialize, Clone)]
+pub struct ContestReschedule {
+    pub contest: Contest,
+    pub old_start_time: DateTime<Utc>,
+    pub old_end_time: Option<DateTime<Utc>>,
+}
+
+// [新增] 改名的比赛 (contest 为新数据)
+#[derive(Debug, Serialize, Clone)]
+pub struct ContestRename {
+    pub contest: Contest,
+    pub old_name: String,
+}
+
+// [新增] 比赛变更类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ContestChangeKind {
+    Rescheduled,
+    Renamed,
+    Cancelled,
+}
+
+// [新增] 一条比赛变更记录
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContestChange {
+    pub contest_id: String,
+    pub platform: String,
+    pub name: String, // 变更后的名称 (取消时为原名称)
+    pub kind: ContestChangeKind,
+    pub detected_at: DateTime<Utc>,
+    pub start_time: DateTime<Utc>, // 变更后的开始时间 (取消时为原定时间)
+    // 仅 rescheduled：原开始时间与移动的分钟数 (正数为推迟)
+    #[serde(default)]
+    pub old_start_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub shift_minutes: Option<i64>,
+    // 仅 renamed
+    #[serde(default)]
+    pub old_name: Option<String>,
+}
+
+// [新增] stats-updated 事件：某个账号的战绩发生变化
+#[derive(Debug, Serialize, Clone)]
+pub struct StatsUpdate {
+    pub platform: String, // 与 RefreshAccount.platform 相同
+    pub handle: String,
+    pub stats: UserStats,
+    pub previous: Option<UserStats>, // 本次启动后首次获取时为 None
+}
+
+// [新增] 各模块单元测试共用的固定时间与工具
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::Contest;
+    use chrono::{DateTime, Duration, Utc};
+
+    // 测试中的 "当前时间"
+    pub fn now() -> DateTime<Utc> {
+        utc("2024-05-01T12:00:00Z")
+    }
+
+    pub fn utc(raw: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(raw)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    // 相对 now 偏移 hours 小时开始、持续 2 小时的比赛
+    pub fn contest(platform: &str, id: &str, name: &str, hours: i64) -> Contest {
+        let start_time = now() + Duration::hours(hours);
+        Contest {
+            name: name.to_string(),
+            start_time,
+            end_time: Some(start_time + Duration::hours(2)),
+            url: format!("https://example.com/{}", id),
+            platform: platform.to_string(),
+            ..Default::default()
+        }
+    }
+}
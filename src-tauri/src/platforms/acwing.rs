@@ -0,0 +1,67 @@
+use crate::models::Contest;
+use crate::time_parse;
+use anyhow::Result;
+use chrono::Utc;
+use regex::Regex;
+
+const ACWING_DOMAIN: &str = "https://www.acwing.com";
+// 竞赛活动列表 (周赛等)
+const ACWING_CONTEST_LIST_URL: &str = "https://www.acwing.com/activity/1/competition/";
+
+// AcWing 个人空间按数字 uid 访问且不公开通过题数，这里只提供比赛列表
+pub async fn fetch_contests() -> Result<Vec<Contest>> {
+    let client = reqwest::Client::builder()
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        .build()?;
+
+    let html = client
+        .get(ACWING_CONTEST_LIST_URL)
+        .send()
+        .await?
+        .text()
+        .await?;
+
+    let re_link = Regex::new(r#"^(\d+)/"#).unwrap();
+    let re_title = Regex::new(r#"activity_title"[^>]*>\s*([^<]+?)\s*<"#).unwrap();
+    let re_tag = Regex::new(r"<[^>]+>").unwrap();
+
+    let now = Utc::now();
+    let mut contests: Vec<Contest> = Vec::new();
+
+    // 每个活动块都以指向 /activity/content/{id}/ 的链接开头
+    for chunk in html.split(r#"href="/activity/content/"#).skip(1) {
+        let id = match re_link.captures(chunk) {
+            Some(caps) => caps[1].to_string(),
+            None => continue,
+        };
+        let name = match re_title.captures(chunk) {
+            Some(caps) => caps[1].to_string(),
+            None => continue,
+        };
+
+        // 块内的时间为北京时间的开始时间
+        let text = re_tag.replace_all(chunk, " ");
+        let start_time = match time_parse::find_datetime(&text, time_parse::china(), now) {
+            Some((t, _)) => t,
+            None => continue,
+        };
+        if start_time <= now {
+            continue;
+        }
+
+        let url = format!("{}/activity/content/{}/", ACWING_DOMAIN, id);
+        if contests.iter().any(|c| c.url == url) {
+            continue;
+        }
+
+        contests.push(Contest {
+            platform: "AcWing".to_string(),
+            name,
+            start_time,
+            url,
+            ..Default::default()
+        });
+    }
+
+    Ok(contests)
+}
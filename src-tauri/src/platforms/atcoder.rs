@@ -1,6 +1,7 @@
-use crate::models::{Contest, UserStats};
+use crate::models::{Contest, RatedRange, RatingEvent, UserStats};
+use crate::time_parse;
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use reqwest::header;
 use scraper::{Html, Selector};
 use serde::Deserialize;
@@ -11,6 +12,24 @@ struct KenkooooAcRank {
     count: u32,
 }
 
+// [新增] /users/{handle}/history/json 的单条记录
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AtCoderHistoryItem {
+    is_rated: bool,
+    place: u32,
+    old_rating: i32,
+    new_rating: i32,
+    performance: i32,
+    // e.g. "abc350.contest.atcoder.jp"
+    contest_screen_name: String,
+    #[serde(default)]
+    contest_name_en: Option<String>,
+    contest_name: String,
+    // e.g. "2024-04-20T22:40:00+09:00"
+    end_time: String,
+}
+
 pub async fn fetch_contests() -> Result<Vec<Contest>> {
     // 1. 请求 AtCoder 官网的比赛列表页面
     let url = "https://atcoder.jp/contests/?lang=en";
@@ -28,9 +47,15 @@ pub async fn fetch_contests() -> Result<Vec<Contest>> {
     let html_content = resp.text().await?;
     let document = Html::parse_document(&html_content);
 
-    let table_selector = Selector::parse("#contest-table-upcoming tbody tr").unwrap();
+    // [修改] 同时解析进行中 (action) 与即将开始 (upcoming) 两张表，列结构一致：
+    // Start Time | Contest Name | Duration | Rated Range
+    let table_selector =
+        Selector::parse("#contest-table-action tbody tr, #contest-table-upcoming tbody tr")
+            .unwrap();
     let time_selector = Selector::parse("td:nth-child(1) time").unwrap();
     let link_selector = Selector::parse("td:nth-child(2) a").unwrap();
+    let duration_selector = Selector::parse("td:nth-child(3)").unwrap();
+    let rated_selector = Selector::parse("td:nth-child(4)").unwrap();
 
     let mut contests = Vec::new();
 
@@ -40,10 +65,12 @@ pub async fn fetch_contests() -> Result<Vec<Contest>> {
             None => continue,
         };
 
-        let start_time = DateTime::parse_from_str(&start_time_str, "%Y-%m-%d %H:%M:%S%z")
-            .context("Failed to parse AtCoder date")?
-            .with_timezone(&Utc);
+        // 形如 "2024-05-04 21:00:00+0900"，自带时区
+        let start_time =
+            time_parse::parse_datetime(&start_time_str, time_parse::japan(), Utc::now())
+                .context("Failed to parse AtCoder date")?;
 
+        // 比赛名称列第一个链接为比赛页 (前面可能还有 rated 颜色图标，但那不是 <a>)
         let anchor = match row.select(&link_selector).next() {
             Some(el) => el,
             None => continue,
@@ -53,17 +80,58 @@ pub async fn fetch_contests() -> Result<Vec<Contest>> {
         let href = anchor.value().attr("href").unwrap_or("");
         let full_url = format!("https://atcoder.jp{}", href);
 
+        // 时长 "HH:MM" (长时间的 Heuristic 比赛可能超过 100 小时)
+        let end_time = row
+            .select(&duration_selector)
+            .next()
+            .and_then(|td| parse_duration(&td.text().collect::<String>()))
+            .map(|d| start_time + d);
+
+        let rated_range = row
+            .select(&rated_selector)
+            .next()
+            .and_then(|td| parse_rated_range(&td.text().collect::<String>()));
+
         contests.push(Contest {
             name,
             start_time,
             url: full_url,
             platform: "AtCoder".to_string(),
+            end_time,
+            rated_range,
+            ..Default::default()
         });
     }
 
     Ok(contests)
 }
 
+/// 解析 "01:40" / "240:00" 形式的比赛时长
+fn parse_duration(text: &str) -> Option<Duration> {
+    let (hours, minutes) = text.trim().split_once(':')?;
+    let hours: i64 = hours.trim().parse().ok()?;
+    let minutes: i64 = minutes.trim().parse().ok()?;
+    Some(Duration::hours(hours) + Duration::minutes(minutes))
+}
+
+/// 解析 Rated Range 列: "All" / "- 1999" / "1200 - 2799" / "1200 -" / "-" (Unrated)
+fn parse_rated_range(text: &str) -> Option<RatedRange> {
+    let text = text.trim();
+    if text.eq_ignore_ascii_case("All") {
+        return Some(RatedRange::default());
+    }
+
+    let (low, high) = text.split_once('-')?;
+    let min = low.trim().parse::<u32>().ok();
+    let max = high.trim().parse::<u32>().ok();
+
+    // 两侧都为空即 "-"，表示该场不计分
+    if min.is_none() && max.is_none() {
+        return None;
+    }
+    Some(RatedRange { min, max })
+}
+
 // [Clean Version]
 // 移除了所有 println! 调试信息
 pub async fn fetch_user_stats(handle: &str) -> Result<UserStats> {
@@ -152,3 +220,70 @@ pub async fn fetch_user_stats(handle: &str) -> Result<UserStats> {
         rating,
     })
 }
+
+// [新增] Rating 历史；只保留 Rated 场次，Unrated 参赛不影响 Rating
+pub async fn fetch_rating_history(handle: &str) -> Result<Vec<RatingEvent>> {
+    let client = reqwest::Client::new();
+    let url = format!("https://atcoder.jp/users/{}/history/json", handle);
+
+    let items: Vec<AtCoderHistoryItem> = client.get(&url).send().await?.json().await?;
+
+    let mut history = Vec::new();
+    for item in items.into_iter().filter(|item| item.is_rated) {
+        // 首场 Rated 的 OldRating 为 0
+        let old_rating = if history.is_empty() {
+            None
+        } else {
+            Some(item.old_rating)
+        };
+        history.push(RatingEvent {
+            platform: "AtCoder".to_string(),
+            contest_id: item
+                .contest_screen_name
+                .split('.')
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            contest_name: item
+                .contest_name_en
+                .filter(|name| !name.is_empty())
+                .unwrap_or(item.contest_name),
+            time: DateTime::parse_from_rfc3339(&item.end_time)
+                .ok()
+                .map(|t| t.with_timezone(&Utc)),
+            old_rating,
+            new_rating: item.new_rating,
+            rank: Some(item.place),
+            performance: Some(item.performance),
+        });
+    }
+
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rated_range_column() {
+        let range = |min, max| Some(RatedRange { min, max });
+        assert_eq!(parse_rated_range("All"), range(None, None));
+        assert_eq!(parse_rated_range(" - 1999 "), range(None, Some(1999)));
+        assert_eq!(
+            parse_rated_range("1200 - 2799"),
+            range(Some(1200), Some(2799))
+        );
+        assert_eq!(parse_rated_range("1200 -"), range(Some(1200), None));
+        assert_eq!(parse_rated_range("-"), None);
+        assert_eq!(parse_rated_range(""), None);
+    }
+
+    #[test]
+    fn parses_contest_duration() {
+        assert_eq!(parse_duration("01:40"), Some(Duration::minutes(100)));
+        assert_eq!(parse_duration(" 240:00 "), Some(Duration::hours(240)));
+        assert_eq!(parse_duration("100"), None);
+        assert_eq!(parse_duration("aa:bb"), None);
+    }
+}
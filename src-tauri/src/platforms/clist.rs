@@ -0,0 +1,152 @@
+// 文件路径: src-tauri/src/platforms/clist.rs
+//
+// clist.by 聚合源 (可选)。配置了 username / api_key 后：
+//   1. 为没有原生爬虫的 OJ 补充比赛；
+//   2. 原生爬虫失败时，用 clist 中该平台的比赛顶替。
+// 原生爬虫成功的平台以原生结果为准，clist 的同平台数据直接丢弃。
+
+use crate::models::{ClistOptions, Contest};
+use anyhow::{anyhow, Result};
+use chrono::{Duration, NaiveDateTime, Utc};
+use reqwest::header::AUTHORIZATION;
+use serde::Deserialize;
+use std::collections::HashSet;
+
+const CLIST_CONTEST_API_URL: &str = "https://clist.by/api/v4/contest/";
+// 时间窗口上限 (天)，设置超出时按上限处理
+const MAX_WINDOW_DAYS: i64 = 365;
+
+// clist resource (站点域名) -> 本应用中的平台名，需与各模块 Contest.platform 一致
+const RESOURCE_PLATFORMS: &[(&str, &str)] = &[
+    ("codeforces.com", "Codeforces"),
+    ("atcoder.jp", "AtCoder"),
+    ("ac.nowcoder.com", "NowCoder"),
+    ("leetcode.com", "LeetCode"),
+    ("leetcode.cn", "LeetCode"),
+    ("acm.hdu.edu.cn", "HDU"),
+    ("luogu.com.cn", "Luogu"),
+    ("codechef.com", "CodeChef"),
+    ("yukicoder.me", "yukicoder"),
+    ("topcoder.com", "TopCoder"),
+    ("acwing.com", "AcWing"),
+    ("uoj.ac", "UOJ"),
+    ("qoj.ac", "QOJ"),
+    ("loj.ac", "LibreOJ"),
+];
+
+#[derive(Deserialize)]
+struct ClistResponse {
+    objects: Vec<ClistContest>,
+}
+
+#[derive(Deserialize)]
+struct ClistContest {
+    // 即站点域名, e.g. "codeforces.com"
+    resource: String,
+    event: String,
+    href: String,
+    // UTC，不带时区后缀, e.g. "2024-05-01T14:35:00"
+    start: String,
+    end: Option<String>,
+}
+
+/// 将 clist 的 resource 映射为平台名；未知站点直接使用域名
+pub fn platform_for_resource(resource: &str) -> String {
+    let resource = resource.trim_start_matches("www.");
+    RESOURCE_PLATFORMS
+        .iter()
+        .find(|(host, _)| *host == resource)
+        .map(|(_, platform)| platform.to_string())
+        .unwrap_or_else(|| resource.to_string())
+}
+
+/// 未配置账号时返回空列表，调用方无需区分
+pub async fn fetch_contests(options: &ClistOptions) -> Result<Vec<Contest>> {
+    let (Some(username), Some(api_key)) = (
+        options.username.as_deref().filter(|s| !s.trim().is_empty()),
+        options.api_key.as_deref().filter(|s| !s.trim().is_empty()),
+    ) else {
+        return Ok(Vec::new());
+    };
+
+    let client = reqwest::Client::new();
+    let now = Utc::now();
+    let window_end = now + Duration::days(options.window_days.clamp(1, MAX_WINDOW_DAYS));
+    let fmt = "%Y-%m-%dT%H:%M:%S";
+
+    let resp = client
+        .get(CLIST_CONTEST_API_URL)
+        .header(
+            AUTHORIZATION,
+            format!("ApiKey {}:{}", username.trim(), api_key.trim()),
+        )
+        .query(&[
+            ("format", "json"),
+            ("order_by", "start"),
+            ("limit", "1000"),
+            ("end__gt", &now.format(fmt).to_string()),
+            ("start__lt", &window_end.format(fmt).to_string()),
+        ])
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        return Err(anyhow!("clist.by API returned status {}", resp.status()));
+    }
+    let data: ClistResponse = resp.json().await?;
+
+    let parse = |raw: &str| {
+        NaiveDateTime::parse_from_str(raw, fmt)
+            .ok()
+            .map(|t| t.and_utc())
+    };
+
+    Ok(data
+        .objects
+        .into_iter()
+        .filter_map(|c| {
+            Some(Contest {
+                platform: platform_for_resource(&c.resource),
+                start_time: parse(&c.start)?,
+                end_time: c.end.as_deref().and_then(parse),
+                name: c.event,
+                url: c.href,
+                host: Some(c.resource),
+                ..Default::default()
+            })
+        })
+        .collect())
+}
+
+/// 把 clist 结果合并进原生结果
+/// `succeeded` 为原生爬虫成功的平台；这些平台的 clist 数据全部跳过，
+/// 其余 clist 比赛再按链接 / (平台, 开始时间, 名称) 去重后追加
+pub fn merge_fallback(
+    contests: &mut Vec<Contest>,
+    succeeded: &HashSet<&str>,
+    fallback: Vec<Contest>,
+) {
+    let key = |c: &Contest| (c.platform.clone(), c.start_time, c.name.to_lowercase());
+
+    let mut seen_urls: HashSet<String> = contests.iter().map(|c| normalize_url(&c.url)).collect();
+    let mut seen_keys: HashSet<_> = contests.iter().map(key).collect();
+
+    for contest in fallback {
+        if succeeded.contains(contest.platform.as_str()) {
+            continue;
+        }
+        if !seen_urls.insert(normalize_url(&contest.url)) || !seen_keys.insert(key(&contest)) {
+            continue;
+        }
+        contests.push(contest);
+    }
+}
+
+// 忽略协议、www 与末尾斜杠的差异
+fn normalize_url(url: &str) -> String {
+    url.trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.")
+        .trim_end_matches('/')
+        .to_lowercase()
+}
@@ -0,0 +1,170 @@
+use crate::models::{CodeChefProfile, Contest, UserStats};
+use crate::time_parse;
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use scraper::{Html, Selector};
+use serde::Deserialize;
+
+const CODECHEF_DOMAIN: &str = "https://www.codechef.com";
+// 比赛列表接口，一次返回进行中 / 未开始 / 已结束三组
+const CODECHEF_CONTEST_LIST_URL: &str = "https://www.codechef.com/api/list/contests/all";
+
+#[derive(Deserialize)]
+struct CcContestListResponse {
+    status: String,
+    #[serde(default)]
+    present_contests: Vec<CcContest>,
+    #[serde(default)]
+    future_contests: Vec<CcContest>,
+}
+
+#[derive(Deserialize)]
+struct CcContest {
+    contest_code: String,
+    contest_name: String,
+    // e.g. "2024-05-01T20:00:00+05:30"
+    contest_start_date_iso: String,
+    contest_end_date_iso: Option<String>,
+}
+
+pub async fn fetch_contests() -> Result<Vec<Contest>> {
+    let client = reqwest::Client::builder()
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        .build()?;
+
+    let resp: CcContestListResponse = client
+        .get(CODECHEF_CONTEST_LIST_URL)
+        .query(&[
+            ("sort_by", "START"),
+            ("sorting_order", "asc"),
+            ("offset", "0"),
+            ("mode", "all"),
+        ])
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    if resp.status != "success" {
+        return Err(anyhow!("CodeChef API returned status: {}", resp.status));
+    }
+
+    let contests = resp
+        .present_contests
+        .into_iter()
+        .chain(resp.future_contests)
+        .filter_map(|c| {
+            let start_time = time_parse::parse_iso(&c.contest_start_date_iso)?;
+            Some(Contest {
+                platform: "CodeChef".to_string(),
+                url: format!("{}/{}", CODECHEF_DOMAIN, c.contest_code),
+                name: c.contest_name,
+                start_time,
+                end_time: c
+                    .contest_end_date_iso
+                    .as_deref()
+                    .and_then(time_parse::parse_iso),
+                ..Default::default()
+            })
+        })
+        .collect();
+
+    Ok(contests)
+}
+
+// 简要战绩由详细战绩汇总而来
+pub async fn fetch_user_stats(handle: &str) -> Result<UserStats> {
+    let profile = fetch_profile(handle).await?;
+
+    let rank = match (profile.stars, profile.global_rank) {
+        (Some(stars), Some(global)) => Some(format!("{}★ Global #{}", stars, global)),
+        (Some(stars), None) => Some(format!("{}★", stars)),
+        (None, Some(global)) => Some(format!("Global #{}", global)),
+        (None, None) => None,
+    };
+
+    Ok(UserStats {
+        platform: "CodeChef".to_string(),
+        handle: profile.handle,
+        solved_count: profile.solved_count,
+        rank,
+        rating: profile.rating,
+    })
+}
+
+// 详细战绩：Rating、星级、全球/国家排名、通过题数 (均来自个人主页)
+pub async fn fetch_profile(handle: &str) -> Result<CodeChefProfile> {
+    let client = reqwest::Client::builder()
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        .build()?;
+
+    let url = format!("{}/users/{}", CODECHEF_DOMAIN, handle);
+    let resp = client.get(&url).send().await?;
+    // 不存在的用户会被重定向回首页
+    if !resp.url().path().starts_with("/users/") {
+        return Err(anyhow!("CodeChef user '{}' not found", handle));
+    }
+    let html = resp.text().await?;
+    let document = Html::parse_document(&html);
+
+    let first_number = |selector: &str| -> Option<u32> {
+        let sel = Selector::parse(selector).unwrap();
+        document
+            .select(&sel)
+            .next()
+            .and_then(|el| leading_number(&el.text().collect::<String>()))
+    };
+
+    // 新用户 Rating 显示为 "0?" 之类的临时值，取前导数字
+    let rating = first_number(".rating-number");
+
+    // 星级：.rating-star 中每颗星一个 <span>；旧版页面为 "3★" 文本
+    let star_sel = Selector::parse(".rating-star span").unwrap();
+    let star_count = document.select(&star_sel).count() as u32;
+    let stars = if star_count > 0 {
+        Some(star_count)
+    } else {
+        first_number("span.rating")
+    };
+
+    // 排名区块依次为 Global Rank、Country Rank，未参加过比赛时显示 "Inactive"
+    let rank_sel = Selector::parse(".rating-ranks ul li strong").unwrap();
+    let ranks: Vec<Option<u32>> = document
+        .select(&rank_sel)
+        .map(|el| leading_number(&el.text().collect::<String>()))
+        .collect();
+    let global_rank = ranks.first().copied().flatten();
+    let country_rank = ranks.get(1).copied().flatten();
+
+    let country_sel = Selector::parse(".user-country-name").unwrap();
+    let country = document
+        .select(&country_sel)
+        .next()
+        .map(|el| el.text().collect::<String>().trim().to_string())
+        .filter(|name| !name.is_empty());
+
+    let re_solved = Regex::new(r"Total Problems Solved:\s*(\d+)").unwrap();
+    let solved_count = re_solved
+        .captures(&html)
+        .and_then(|c| c[1].parse().ok())
+        .unwrap_or(0);
+
+    Ok(CodeChefProfile {
+        handle: handle.to_string(),
+        rating,
+        stars,
+        global_rank,
+        country_rank,
+        country,
+        solved_count,
+    })
+}
+
+fn leading_number(text: &str) -> Option<u32> {
+    let digits: String = text
+        .trim()
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse().ok()
+}
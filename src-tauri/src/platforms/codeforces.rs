@@ -1,4 +1,4 @@
-use crate::models::{Contest, UserStats};
+use crate::models::{Contest, RatingEvent, UserStats};
 use anyhow::Result;
 use chrono::{TimeZone, Utc};
 use serde::Deserialize;
@@ -8,6 +8,8 @@ const CF_API_URL: &str = "https://codeforces.com/api/contest.list";
 const CF_USER_STATUS_URL: &str = "https://codeforces.com/api/user.status";
 // [新增] 用户信息接口
 const CF_USER_INFO_URL: &str = "https://codeforces.com/api/user.info";
+// [新增] Rating 变化记录接口
+const CF_USER_RATING_URL: &str = "https://codeforces.com/api/user.rating";
 
 #[derive(Deserialize)]
 struct CfResponse {
@@ -64,6 +66,28 @@ struct CfUserInfo {
     // maxRating: Option<u32>, // 也可以获取最高分，如果需要的话
 }
 
+// [新增] Rating 变化记录响应结构
+#[derive(Deserialize)]
+struct CfRatingResponse {
+    status: String,
+    // 失败时没有 result，只有 comment
+    #[serde(default)]
+    result: Vec<CfRatingChange>,
+    #[serde(default)]
+    comment: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CfRatingChange {
+    contest_id: u64,
+    contest_name: String,
+    rank: u32,
+    rating_update_time_seconds: i64,
+    old_rating: i32,
+    new_rating: i32,
+}
+
 pub async fn fetch_contests() -> Result<Vec<Contest>> {
     let client = reqwest::Client::builder()
         .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
@@ -95,6 +119,7 @@ pub async fn fetch_contests() -> Result<Vec<Contest>> {
                             start_time: st,
                             url: format!("https://codeforces.com/contests/{}", c.id),
                             platform: "Codeforces".to_string(),
+                            ..Default::default()
                         });
                     }
                 }
@@ -157,3 +182,42 @@ pub async fn fetch_user_stats(handle: &str) -> Result<UserStats> {
         rating: user_info.rating,
     })
 }
+
+// [新增] Rating 历史，接口本身即按时间升序
+pub async fn fetch_rating_history(handle: &str) -> Result<Vec<RatingEvent>> {
+    let client = reqwest::Client::new();
+
+    let resp: CfRatingResponse = client
+        .get(CF_USER_RATING_URL)
+        .query(&[("handle", handle)])
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    if resp.status != "OK" {
+        return Err(anyhow::anyhow!(
+            "Failed to fetch rating history: {}",
+            resp.comment.unwrap_or_default()
+        ));
+    }
+
+    Ok(resp
+        .result
+        .into_iter()
+        .enumerate()
+        .map(|(i, change)| RatingEvent {
+            platform: "Codeforces".to_string(),
+            contest_id: change.contest_id.to_string(),
+            contest_name: change.contest_name,
+            time: Utc
+                .timestamp_opt(change.rating_update_time_seconds, 0)
+                .single(),
+            // 首场的 oldRating 是系统给的初始值 (0 或 1400)，不算真实 Rating
+            old_rating: (i > 0).then_some(change.old_rating),
+            new_rating: change.new_rating,
+            rank: Some(change.rank),
+            performance: None,
+        })
+        .collect())
+}
@@ -0,0 +1,457 @@
+// 文件路径: src-tauri/src/platforms/custom.rs
+//
+// 声明式爬虫：从应用配置目录的 scrapers/ 下读取 *.toml / *.json 定义，
+// 每个文件描述一个站点 (列表页 URL、行/字段选择器或正则、时间格式与时区)，
+// 产出与内置模块相同的 Contest。每次抓取都重新读取文件，修改后刷新即可生效。
+//
+// CSS 模式示例 (TOML):
+//   platform = "SDUT OJ"
+//   url = "https://acm.sdut.edu.cn/onlinejudge3/contests"
+//   rows = "table.contest-list tbody tr"
+//   name = "td:nth-child(2) a"
+//   start_time = "td:nth-child(3)"
+//   end_time = "td:nth-child(4)"
+//   time_format = "%Y-%m-%d %H:%M"
+//   timezone = "+08:00"
+//
+// 正则模式：提供 row_regex，使用命名分组 name / link / start / end，其余字段选择器不再需要。
+
+use crate::models::{Contest, ScraperError};
+use crate::time_parse;
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use regex::Regex;
+use reqwest::Url;
+use scraper::{ElementRef, Html, Selector};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tokio::task::JoinSet;
+
+// 配置目录，由 lib.rs 在 setup 阶段设置
+static CONFIG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// 设置应用配置目录，只在启动时调用一次
+pub fn set_config_dir(dir: PathBuf) {
+    let _ = CONFIG_DIR.set(dir);
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScraperDefinition {
+    // 显示用的平台名
+    pub platform: String,
+    pub url: String,
+    // 解析相对链接的基准地址，缺省为 url 本身
+    #[serde(default)]
+    pub base_url: Option<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    // --- CSS 模式：rows 选中每一行，其余选择器相对于行 ---
+    #[serde(default)]
+    pub rows: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    // 取该元素的 href，缺省使用 name 选中的元素
+    #[serde(default)]
+    pub link: Option<String>,
+    #[serde(default)]
+    pub start_time: Option<String>,
+    #[serde(default)]
+    pub end_time: Option<String>,
+
+    // --- 正则模式：命名分组 name / link / start / end ---
+    #[serde(default)]
+    pub row_regex: Option<String>,
+
+    // chrono 格式串, e.g. "%Y-%m-%d %H:%M:%S"；缺省时自动识别常见格式
+    #[serde(default)]
+    pub time_format: Option<String>,
+    // "+08:00" / "UTC+9" / "UTC"，缺省为北京时间
+    #[serde(default)]
+    pub timezone: Option<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// 从文件读取一个定义，按扩展名区分 TOML / JSON
+pub fn load_definition(path: &Path) -> Result<ScraperDefinition> {
+    let text = std::fs::read_to_string(path)?;
+    let def: ScraperDefinition = match path.extension().and_then(|e| e.to_str()) {
+        Some("toml") => toml::from_str(&text)?,
+        Some("json") => serde_json::from_str(&text)?,
+        _ => return Err(anyhow!("Unsupported scraper file: {}", path.display())),
+    };
+
+    if def.row_regex.is_none() && (def.rows.is_none() || def.name.is_none()) {
+        return Err(anyhow!(
+            "{}: either row_regex or rows + name is required",
+            path.display()
+        ));
+    }
+    Ok(def)
+}
+
+// 逐个读取 scrapers/ 下的文件，保留各自的结果
+fn scan_definitions() -> Vec<(PathBuf, Result<ScraperDefinition>)> {
+    let Some(dir) = CONFIG_DIR.get().map(|d| d.join("scrapers")) else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let def = load_definition(&path);
+            (path, def)
+        })
+        .collect()
+}
+
+/// 读取 scrapers/ 下所有启用的定义；有误的文件跳过 (见 definition_errors)
+fn load_definitions() -> Vec<ScraperDefinition> {
+    scan_definitions()
+        .into_iter()
+        .filter_map(|(_, def)| def.ok())
+        .filter(|def| def.enabled)
+        .collect()
+}
+
+/// 无法加载的定义文件及原因，供设置页提示
+pub fn definition_errors() -> Vec<ScraperError> {
+    scan_definitions()
+        .into_iter()
+        .filter_map(|(path, def)| {
+            let error = def.err()?;
+            Some(ScraperError {
+                file: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                error: format!("{:#}", error),
+            })
+        })
+        .collect()
+}
+
+pub async fn fetch_contests() -> Result<Vec<Contest>> {
+    let client = reqwest::Client::builder()
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        .build()?;
+
+    // 各站点并发抓取，忽略单个站点的失败
+    let mut tasks = JoinSet::new();
+    for def in load_definitions() {
+        let client = client.clone();
+        tasks.spawn(async move { fetch_with(&client, &def).await });
+    }
+
+    let mut contests = Vec::new();
+    while let Some(res) = tasks.join_next().await {
+        if let Ok(Ok(c)) = res {
+            contests.extend(c);
+        }
+    }
+
+    Ok(contests)
+}
+
+async fn fetch_with(client: &reqwest::Client, def: &ScraperDefinition) -> Result<Vec<Contest>> {
+    let html = client.get(&def.url).send().await?.text().await?;
+    extract(def, &html, Utc::now())
+}
+
+/// 按定义从页面中提取比赛，只保留尚未结束的场次
+pub fn extract(def: &ScraperDefinition, html: &str, now: DateTime<Utc>) -> Result<Vec<Contest>> {
+    let tz = match def.timezone.as_deref() {
+        Some(raw) => parse_timezone(raw).with_context(|| format!("Invalid timezone: {}", raw))?,
+        None => time_parse::china(),
+    };
+    let base = match def.base_url.as_deref() {
+        Some(base) => Url::parse(base)?,
+        None => Url::parse(&def.url)?,
+    };
+
+    let parse_time = |text: &str| -> Option<DateTime<Utc>> {
+        let text = text.trim();
+        match def.time_format.as_deref() {
+            Some(fmt) => NaiveDateTime::parse_from_str(text, fmt)
+                .ok()
+                .and_then(|naive| tz.from_local_datetime(&naive).single())
+                .map(|t| t.with_timezone(&Utc)),
+            None => time_parse::find_datetime(text, tz, now).map(|(t, _)| t),
+        }
+    };
+
+    // (name, link, start, end) 原始文本
+    let raw_rows = match def.row_regex.as_deref() {
+        Some(pattern) => extract_regex_rows(pattern, html)?,
+        None => extract_css_rows(def, html)?,
+    };
+
+    let mut contests = Vec::new();
+    for (name, link, start, end) in raw_rows {
+        let Some(start_time) = parse_time(&start) else {
+            continue;
+        };
+        let end_time = end.as_deref().and_then(parse_time);
+        if end_time.unwrap_or(start_time) <= now {
+            continue;
+        }
+
+        let url = link
+            .and_then(|href| base.join(href.trim()).ok())
+            .map(|u| u.to_string())
+            .unwrap_or_else(|| def.url.clone());
+
+        contests.push(Contest {
+            platform: def.platform.clone(),
+            name,
+            start_time,
+            url,
+            end_time,
+            ..Default::default()
+        });
+    }
+
+    Ok(contests)
+}
+
+type RawRow = (String, Option<String>, String, Option<String>);
+
+fn extract_css_rows(def: &ScraperDefinition, html: &str) -> Result<Vec<RawRow>> {
+    let parse =
+        |sel: &str| Selector::parse(sel).map_err(|e| anyhow!("Invalid selector '{}': {}", sel, e));
+    let parse_opt = |sel: Option<&String>| sel.map(|s| parse(s)).transpose();
+
+    let rows = parse(def.rows.as_deref().unwrap_or_default())?;
+    let name = parse(def.name.as_deref().unwrap_or_default())?;
+    let link = parse_opt(def.link.as_ref())?;
+    let start = parse_opt(def.start_time.as_ref())?;
+    let end = parse_opt(def.end_time.as_ref())?;
+
+    let text_of = |el: ElementRef| el.text().collect::<Vec<_>>().join(" ").trim().to_string();
+
+    let document = Html::parse_document(html);
+    let mut result = Vec::new();
+    for row in document.select(&rows) {
+        let Some(name_el) = row.select(&name).next() else {
+            continue;
+        };
+        let link_el = match &link {
+            Some(sel) => row.select(sel).next(),
+            None => Some(name_el),
+        };
+        // 未指定开始时间列时在整行文本中查找
+        let start_text = match &start {
+            Some(sel) => row.select(sel).next().map(text_of),
+            None => Some(text_of(row)),
+        };
+        let Some(start_text) = start_text else {
+            continue;
+        };
+
+        result.push((
+            text_of(name_el),
+            link_el.and_then(|el| el.value().attr("href").map(str::to_string)),
+            start_text,
+            end.as_ref()
+                .and_then(|sel| row.select(sel).next())
+                .map(text_of),
+        ));
+    }
+    Ok(result)
+}
+
+fn extract_regex_rows(pattern: &str, html: &str) -> Result<Vec<RawRow>> {
+    let re = Regex::new(pattern)?;
+    let re_tag = Regex::new(r"<[^>]+>").unwrap();
+    let group = |caps: &regex::Captures, key: &str| {
+        caps.name(key)
+            .map(|m| re_tag.replace_all(m.as_str(), "").trim().to_string())
+    };
+
+    Ok(re
+        .captures_iter(html)
+        .filter_map(|caps| {
+            Some((
+                group(&caps, "name")?,
+                group(&caps, "link"),
+                group(&caps, "start")?,
+                group(&caps, "end"),
+            ))
+        })
+        .collect())
+}
+
+/// "+08:00" / "+0800" / "UTC+8" / "GMT-5" / "UTC"
+fn parse_timezone(raw: &str) -> Option<FixedOffset> {
+    let raw = raw.trim().to_ascii_uppercase();
+    let rest = raw
+        .strip_prefix("UTC")
+        .or_else(|| raw.strip_prefix("GMT"))
+        .unwrap_or(&raw);
+    if rest.is_empty() || rest == "Z" {
+        return FixedOffset::east_opt(0);
+    }
+
+    let sign = match rest.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let body = &rest[1..];
+    let (hours, minutes) = match body.split_once(':') {
+        Some((h, m)) => (h.parse::<i32>().ok()?, m.parse::<i32>().ok()?),
+        None if body.len() == 4 => (body[..2].parse().ok()?, body[2..].parse().ok()?),
+        None => (body.parse().ok()?, 0),
+    };
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_support::{now, utc};
+
+    const TABLE_HTML: &str = r#"
+        <table class="contest-list"><tbody>
+          <tr>
+            <td>1001</td>
+            <td><a href="/contests/1001">Weekly Round 1</a></td>
+            <td>2024-05-02 19:00</td>
+            <td>2024-05-02 21:00</td>
+          </tr>
+          <tr>
+            <td>1000</td>
+            <td><a href="/contests/1000">Weekly Round 0</a></td>
+            <td>2024-04-25 19:00</td>
+            <td>2024-04-25 21:00</td>
+          </tr>
+          <tr>
+            <td>999</td>
+            <td><a href="/contests/999">Practice</a></td>
+            <td>TBD</td>
+            <td></td>
+          </tr>
+        </tbody></table>
+    "#;
+
+    fn definition(toml_text: &str) -> ScraperDefinition {
+        toml::from_str(toml_text).unwrap()
+    }
+
+    #[test]
+    fn extracts_rows_with_css_selectors() {
+        let def = definition(
+            r#"
+            platform = "Example OJ"
+            url = "https://oj.example.com/contests"
+            rows = "table.contest-list tbody tr"
+            name = "td:nth-child(2) a"
+            start_time = "td:nth-child(3)"
+            end_time = "td:nth-child(4)"
+            time_format = "%Y-%m-%d %H:%M"
+            timezone = "+08:00"
+            "#,
+        );
+
+        // 已结束与时间无法解析的行被跳过
+        let contests = extract(&def, TABLE_HTML, now()).unwrap();
+        assert_eq!(contests.len(), 1);
+        let contest = &contests[0];
+        assert_eq!(contest.platform, "Example OJ");
+        assert_eq!(contest.name, "Weekly Round 1");
+        assert_eq!(contest.url, "https://oj.example.com/contests/1001");
+        assert_eq!(contest.start_time, utc("2024-05-02T11:00:00Z"));
+        assert_eq!(contest.end_time, Some(utc("2024-05-02T13:00:00Z")));
+    }
+
+    #[test]
+    fn finds_start_time_in_row_text_without_selector() {
+        let def = definition(
+            r#"
+            platform = "Example OJ"
+            url = "https://oj.example.com/contests"
+            base_url = "https://www.example.com/"
+            rows = "table.contest-list tbody tr"
+            name = "td:nth-child(2) a"
+            timezone = "UTC+9"
+            "#,
+        );
+
+        let contests = extract(&def, TABLE_HTML, now()).unwrap();
+        assert_eq!(contests.len(), 1);
+        assert_eq!(contests[0].url, "https://www.example.com/contests/1001");
+        assert_eq!(contests[0].start_time, utc("2024-05-02T10:00:00Z"));
+        assert_eq!(contests[0].end_time, None);
+    }
+
+    #[test]
+    fn extracts_rows_with_regex() {
+        let def = definition(
+            r#"
+            platform = "Example OJ"
+            url = "https://oj.example.com/contests"
+            row_regex = '<a href="(?P<link>[^"]+)">(?P<name>[^<]+)</a></td>\s*<td>(?P<start>[^<]+)</td>\s*<td>(?P<end>[^<]*)</td>'
+            "#,
+        );
+
+        let contests = extract(&def, TABLE_HTML, now()).unwrap();
+        assert_eq!(contests.len(), 1);
+        assert_eq!(contests[0].name, "Weekly Round 1");
+        assert_eq!(contests[0].start_time, utc("2024-05-02T11:00:00Z"));
+        assert_eq!(contests[0].end_time, Some(utc("2024-05-02T13:00:00Z")));
+    }
+
+    #[test]
+    fn rejects_invalid_selector_and_timezone() {
+        let mut def = definition(
+            r#"
+            platform = "Example OJ"
+            url = "https://oj.example.com/contests"
+            rows = "tr["
+            name = "a"
+            "#,
+        );
+        assert!(extract(&def, TABLE_HTML, now()).is_err());
+
+        def.rows = Some("tr".to_string());
+        def.timezone = Some("Asia/Shanghai".to_string());
+        assert!(extract(&def, TABLE_HTML, now()).is_err());
+    }
+
+    #[test]
+    fn parses_timezone_forms() {
+        let hours = |raw: &str| parse_timezone(raw).map(|tz| tz.local_minus_utc() / 3600);
+        assert_eq!(hours("+08:00"), Some(8));
+        assert_eq!(hours("+0900"), Some(9));
+        assert_eq!(hours("UTC+8"), Some(8));
+        assert_eq!(hours("GMT-5"), Some(-5));
+        assert_eq!(hours("UTC"), Some(0));
+        assert_eq!(hours("CST"), None);
+    }
+
+    #[test]
+    fn reports_incomplete_definition_file() {
+        let path = std::env::temp_dir().join(format!("cpc-scraper-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "platform = \"Example OJ\"\nurl = \"https://oj.example.com/\"\n",
+        )
+        .unwrap();
+        let res = load_definition(&path);
+        let _ = std::fs::remove_file(&path);
+        assert!(res.unwrap_err().to_string().contains("row_regex"));
+    }
+}
@@ -1,20 +1,28 @@
-use crate::models::{Contest, UserStats};
+use crate::models::{Contest, DaimayuanProfile, DaimayuanRatingEntry, RatingEvent, UserStats};
+use crate::time_parse;
 use anyhow::Result;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use regex::Regex;
+use std::collections::HashSet;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+const DAIMAYUAN_DOMAIN: &str = "https://bs.daimayuan.top";
+// 评测记录最多翻的页数
+const MAX_RECORD_PAGES: u32 = 5;
+
 pub async fn fetch_contests() -> Result<Vec<Contest>> {
     let client = reqwest::Client::builder()
         .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
         .build()?;
 
-    let url = "https://bs.daimayuan.top/contest";
-    let html = client.get(url).send().await?.text().await?;
+    let url = format!("{}/contest", DAIMAYUAN_DOMAIN);
+    let html = client.get(&url).send().await?.text().await?;
 
     let re_title_link =
         Regex::new(r#"contest__title"><a\s+href="(/contest/[^"]+)"[^>]*>([^<]+)</a>"#).unwrap();
     let re_timestamp = Regex::new(r#"data-timestamp="(\d+)""#).unwrap();
+    // 比赛信息栏中的时长，如 "3 小时" / "2.5 hour(s)"
+    let re_duration = Regex::new(r"(\d+(?:\.\d+)?)\s*(?:小时|hour)").unwrap();
 
     let mut contests = Vec::new();
     let now_sec = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
@@ -32,20 +40,27 @@ pub async fn fetch_contests() -> Result<Vec<Contest>> {
 
         let (link, name) = match re_title_link.captures(chunk) {
             Some(caps) => (
-                format!("https://bs.daimayuan.top{}", &caps[1]),
+                format!("{}{}", DAIMAYUAN_DOMAIN, &caps[1]),
                 caps[2].trim().to_string(),
             ),
             None => continue,
         };
 
         let start_time_utc: DateTime<Utc> =
-            DateTime::from_timestamp(timestamp_i64, 0).unwrap_or_else(|| Utc::now());
+            DateTime::from_timestamp(timestamp_i64, 0).unwrap_or_else(Utc::now);
+
+        let end_time = re_duration
+            .captures(chunk)
+            .and_then(|caps| caps[1].parse::<f64>().ok())
+            .map(|hours| start_time_utc + Duration::minutes((hours * 60.0).round() as i64));
 
         contests.push(Contest {
             platform: "Daimayuan".to_string(),
             name,
             start_time: start_time_utc,
             url: link,
+            end_time,
+            ..Default::default()
         });
     }
 
@@ -57,25 +72,120 @@ pub async fn fetch_user_stats(handle: &str) -> Result<UserStats> {
         .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
         .build()?;
 
-    let url = format!("https://bs.daimayuan.top/user/{}", handle);
+    let url = format!("{}/user/{}", DAIMAYUAN_DOMAIN, handle);
+    let html = client.get(&url).send().await?.text().await?;
+
+    let solved_count = parse_solved_count(&html);
+    // 最近一次 Rating 即历史中的最后一项
+    let latest_rating = parse_rating_history(&html).last().map(|e| e.new_rating);
+
+    Ok(UserStats {
+        platform: "Daimayuan".to_string(),
+        handle: handle.to_string(),
+        rating: latest_rating,
+        solved_count,
+        rank: None,
+    })
+}
+
+// [新增] 详细战绩：Rating 历史 + 已通过题目列表
+pub async fn fetch_profile(handle: &str) -> Result<DaimayuanProfile> {
+    let client = reqwest::Client::builder()
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        .build()?;
+
+    let url = format!("{}/user/{}", DAIMAYUAN_DOMAIN, handle);
     let html = client.get(&url).send().await?.text().await?;
 
-    // 1. 匹配刷题数 - 恢复最稳健的“锚点+内容”逻辑
-    // 逻辑：必须在同一个 numbox 容器内找到数字，且其后紧跟“已通过”
-    // 使用 (?s) 开启单行模式，使用 [\s\S]*? 跨越可能的任何换行和标签
+    let rating_history = parse_rating_history(&html);
+
+    // 评测记录页按 status=1 (Accepted) 过滤，逐页收集题号直到空页
+    let re_problem = Regex::new(r#"href="/p/([^"/?#]+)""#).unwrap();
+    let mut seen = HashSet::new();
+    let mut solved_problems = Vec::new();
+    for page in 1..=MAX_RECORD_PAGES {
+        let record_url = format!("{}/record", DAIMAYUAN_DOMAIN);
+        let page_html = match client
+            .get(&record_url)
+            .query(&[
+                ("uidOrName", handle),
+                ("status", "1"),
+                ("page", &page.to_string()),
+            ])
+            .send()
+            .await
+        {
+            Ok(resp) => resp.text().await.unwrap_or_default(),
+            Err(_) => break,
+        };
+
+        let before = seen.len();
+        for caps in re_problem.captures_iter(&page_html) {
+            if seen.insert(caps[1].to_string()) {
+                solved_problems.push(caps[1].to_string());
+            }
+        }
+        if seen.len() == before {
+            break;
+        }
+    }
+
+    Ok(DaimayuanProfile {
+        handle: handle.to_string(),
+        rating: rating_history.last().map(|e| e.new_rating),
+        solved_count: parse_solved_count(&html),
+        rating_history,
+        solved_problems,
+    })
+}
+
+// [新增] 统一格式的 Rating 历史
+pub async fn fetch_rating_history(handle: &str) -> Result<Vec<RatingEvent>> {
+    let client = reqwest::Client::builder()
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        .build()?;
+
+    let url = format!("{}/user/{}", DAIMAYUAN_DOMAIN, handle);
+    let html = client.get(&url).send().await?.text().await?;
+
+    Ok(parse_rating_history(&html)
+        .into_iter()
+        .map(|entry| RatingEvent {
+            platform: "Daimayuan".to_string(),
+            // 比赛链接形如 /contest/{id}，取最后一段作为 id
+            contest_id: entry
+                .contest_url
+                .as_deref()
+                .and_then(|u| u.trim_end_matches('/').rsplit('/').next())
+                .unwrap_or_default()
+                .to_string(),
+            contest_name: entry.contest_name,
+            time: entry.time,
+            old_rating: entry.old_rating.map(|r| r as i32),
+            new_rating: entry.new_rating as i32,
+            rank: entry.rank,
+            performance: None,
+        })
+        .collect())
+}
+
+// 1. 匹配刷题数 - 恢复最稳健的“锚点+内容”逻辑
+// 逻辑：必须在同一个 numbox 容器内找到数字，且其后紧跟“已通过”
+// 使用 (?s) 开启单行模式，使用 [\s\S]*? 跨越可能的任何换行和标签
+fn parse_solved_count(html: &str) -> u32 {
     let re_solved = Regex::new(
         r#"(?s)<div[^>]*class="numbox"[^>]*>[\s\S]*?numbox__num[^>]*>(\d+)</div>[\s\S]*?已通过"#,
     )
     .unwrap();
 
-    let solved_count = re_solved
-        .captures(&html)
+    re_solved
+        .captures(html)
         .and_then(|cap| cap[1].parse::<u32>().ok())
         .unwrap_or_else(|| {
             // 如果上述精准匹配失败，尝试寻找页面上第二个 numbox 数字（代码源主页固定结构：1.排名 2.通过）
             let re_any_numbox = Regex::new(r#"class="numbox__num[^>]*>(\d+)</div>"#).unwrap();
             let all_nums: Vec<u32> = re_any_numbox
-                .captures_iter(&html)
+                .captures_iter(html)
                 .map(|c| c[1].parse::<u32>().unwrap_or(0))
                 .collect();
             if all_nums.len() >= 2 {
@@ -83,19 +193,66 @@ pub async fn fetch_user_stats(handle: &str) -> Result<UserStats> {
             } else {
                 0
             }
-        });
+        })
+}
 
-    // 2. 匹配最近一次 Rating - 第一个匹配项即为最新
-    let re_rating = Regex::new(r#"class="col--new_rating"[^>]*>(\d+)</td>"#).unwrap();
-    let latest_rating = re_rating
-        .captures(&html)
-        .and_then(|cap| cap[1].parse::<u32>().ok());
+// 2. Rating 记录表：每行含 col--new_rating，其余列 (比赛、排名、旧 Rating、时间) 尽量解析
+// 页面中最新的记录在最前，这里转为时间升序
+fn parse_rating_history(html: &str) -> Vec<DaimayuanRatingEntry> {
+    let re_row = Regex::new(r#"(?s)<tr[^>]*>(.*?)</tr>"#).unwrap();
+    let re_cell = |class: &str| {
+        Regex::new(&format!(
+            r#"(?s)class="col--{}[^"]*"[^>]*>(.*?)</td>"#,
+            class
+        ))
+        .unwrap()
+    };
+    let re_new = re_cell("new_rating");
+    let re_old = re_cell("old_rating");
+    let re_rank = re_cell("rank");
+    let re_contest = re_cell("contest");
+    let re_time = re_cell("time");
+    let re_link = Regex::new(r#"href="([^"]+)""#).unwrap();
+    let re_tag = Regex::new(r"<[^>]+>").unwrap();
+    let re_timestamp = Regex::new(r#"data-timestamp="(\d+)""#).unwrap();
 
-    Ok(UserStats {
-        platform: "Daimayuan".to_string(),
-        handle: handle.to_string(),
-        rating: latest_rating,
-        solved_count,
-        rank: None,
-    })
+    let strip = |raw: &str| re_tag.replace_all(raw, "").trim().to_string();
+    let number = |raw: &str| strip(raw).parse::<u32>().ok();
+
+    let mut history: Vec<DaimayuanRatingEntry> = re_row
+        .captures_iter(html)
+        .filter_map(|row| {
+            let row = &row[1];
+            let new_rating = number(&re_new.captures(row)?[1])?;
+            let contest_cell = re_contest.captures(row).map(|c| c[1].to_string());
+
+            let time = re_time.captures(row).and_then(|c| {
+                let cell = &c[1];
+                match re_timestamp.captures(cell) {
+                    Some(ts) => DateTime::from_timestamp(ts[1].parse().ok()?, 0),
+                    None => {
+                        time_parse::parse_datetime(&strip(cell), time_parse::china(), Utc::now())
+                    }
+                }
+            });
+
+            Some(DaimayuanRatingEntry {
+                contest_name: contest_cell.as_deref().map(strip).unwrap_or_default(),
+                contest_url: contest_cell
+                    .as_deref()
+                    .and_then(|cell| re_link.captures(cell))
+                    .map(|c| format!("{}{}", DAIMAYUAN_DOMAIN, &c[1])),
+                rank: re_rank.captures(row).and_then(|c| number(&c[1])),
+                old_rating: re_old.captures(row).and_then(|c| number(&c[1])),
+                new_rating,
+                time,
+            })
+        })
+        .collect();
+
+    history.reverse();
+    if history.iter().all(|e| e.time.is_some()) {
+        history.sort_by_key(|e| e.time);
+    }
+    history
 }
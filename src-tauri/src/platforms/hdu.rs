@@ -1,14 +1,58 @@
-use crate::models::Contest;
-use anyhow::Result;
-use chrono::{FixedOffset, NaiveDateTime, TimeZone, Utc};
+use crate::models::{Contest, HduContestOptions, HduProfile, UserStats};
+use crate::time_parse;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use regex::Regex;
 use scraper::{Html, Selector};
 
 // HDU 列表地址
 const HDU_URL: &str = "https://acm.hdu.edu.cn/contests/contest_list.php";
 // [修改] 只保留域名，用于重组 URL
 const HDU_DOMAIN: &str = "https://acm.hdu.edu.cn";
+// [新增] 用户状态页
+const HDU_USER_STATUS_URL: &str = "https://acm.hdu.edu.cn/userstatus.php";
+
+// 列表各列的下标；HDU 改版过多次，优先按表头文字定位
+struct Columns {
+    name: usize,
+    start: usize,
+    end: Option<usize>,
+    status: Option<usize>,
+    access: Option<usize>,
+}
+
+impl Default for Columns {
+    // 旧版列表: ID | Contest Name | Date | Status | Type
+    fn default() -> Self {
+        Self {
+            name: 1,
+            start: 2,
+            end: None,
+            status: Some(3),
+            access: Some(4),
+        }
+    }
+}
+
+impl Columns {
+    fn from_header(cells: &[String]) -> Option<Self> {
+        let find = |keys: &[&str]| {
+            cells
+                .iter()
+                .position(|c| keys.iter().any(|k| c.eq_ignore_ascii_case(k)))
+        };
+        Some(Self {
+            name: find(&["Contest Name", "Name", "Title"])?,
+            start: find(&["Start Time", "Date", "Begin Time"])?,
+            end: find(&["End Time"]),
+            status: find(&["Status"]),
+            access: find(&["Type", "Access"]),
+        })
+    }
+}
 
-pub async fn fetch_contests() -> Result<Vec<Contest>> {
+// [修改] 返回全部未结束的比赛 (含进行中)，数量限制由调用方决定
+pub async fn fetch_contests(options: &HduContestOptions) -> Result<Vec<Contest>> {
     // 1. 发起请求
     let client = reqwest::Client::builder()
         .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
@@ -21,30 +65,34 @@ pub async fn fetch_contests() -> Result<Vec<Contest>> {
     // 2. 定义选择器
     let row_selector = Selector::parse("tr").unwrap();
     let link_selector = Selector::parse("a").unwrap();
-    let td_selector = Selector::parse("td").unwrap();
+    let cell_selector = Selector::parse("th, td").unwrap();
 
     let mut contests = Vec::new();
+    let mut columns = Columns::default();
+    let now = Utc::now();
 
     // 3. 解析表格
     for row in document.select(&row_selector) {
-        let tds: Vec<_> = row.select(&td_selector).collect();
+        let cells: Vec<_> = row.select(&cell_selector).collect();
+        let texts: Vec<String> = cells
+            .iter()
+            .map(|el| el.text().collect::<Vec<_>>().join("").trim().to_string())
+            .collect();
+
+        // 表头行：记录列位置后跳过
+        if let Some(header_columns) = Columns::from_header(&texts) {
+            columns = header_columns;
+            continue;
+        }
 
         // 至少需要有 ID, Name, Date 这几列
-        if tds.len() < 3 {
+        if cells.len() <= columns.name.max(columns.start) {
             continue;
         }
 
-        // 提取名称 (Index 1)
-        let name_el = match tds.get(1) {
-            Some(el) => el,
-            None => continue,
-        };
-        let raw_name = name_el
-            .text()
-            .collect::<Vec<_>>()
-            .join("")
-            .trim()
-            .to_string();
+        // 提取名称
+        let name_el = cells[columns.name];
+        let raw_name = texts[columns.name].clone();
 
         // 过滤无效行
         if raw_name.is_empty() || raw_name == "Problem Archive" || raw_name.contains("Contest Name")
@@ -59,13 +107,13 @@ pub async fn fetch_contests() -> Result<Vec<Contest>> {
                 if href.is_empty() {
                     continue;
                 }
-                
+
                 // 核心修复：不直接拼接，而是提取 cid 重组
                 if let Some(idx) = href.find("cid=") {
                     let cid_str = &href[idx + 4..];
                     // 处理可能存在的后续参数 (虽然 HDU 列表页通常没有，但为了健壮性)
                     let cid = cid_str.split('&').next().unwrap_or(cid_str);
-                    
+
                     // 强制生成你需要的格式: https://acm.hdu.edu.cn/contest/problems?cid=1197
                     format!("{}/contest/problems?cid={}", HDU_DOMAIN, cid)
                 } else {
@@ -83,45 +131,124 @@ pub async fn fetch_contests() -> Result<Vec<Contest>> {
             None => continue,
         };
 
-        // 提取时间 (Index 2)
-        let time_str = match tds.get(2) {
-            Some(el) => el.text().collect::<Vec<_>>().join("").trim().to_string(),
-            None => continue,
-        };
-
         // 4. 解析时间
-        let start_time = match NaiveDateTime::parse_from_str(&time_str, "%Y-%m-%d %H:%M:%S") {
-            Ok(dt) => {
-                let offset = FixedOffset::east_opt(8 * 3600).unwrap();
-                match offset.from_local_datetime(&dt).single() {
-                    Some(local_dt) => local_dt.with_timezone(&Utc),
-                    None => continue,
-                }
-            }
-            Err(_) => continue, // 解析失败直接跳过
+        let start_time = match parse_hdu_time(&texts[columns.start]) {
+            Some(t) => t,
+            None => continue, // 解析失败直接跳过
         };
+        let end_time = columns
+            .end
+            .and_then(|i| texts.get(i))
+            .and_then(|t| parse_hdu_time(t));
+
+        // 5. 过滤已结束的比赛：优先看状态列，其次看时间
+        let status = columns.status.and_then(|i| texts.get(i));
+        let finished = match status.map(|s| s.to_ascii_lowercase()) {
+            // 注意 "Pending" 也包含 "end"，需先判断
+            Some(s) if s.contains("running") || s.contains("pending") => false,
+            Some(s) if s.contains("ended") => true,
+            _ => end_time.unwrap_or(start_time) <= now,
+        };
+        if finished {
+            continue;
+        }
+
+        // Public / Private / Register
+        let access = columns
+            .access
+            .and_then(|i| texts.get(i))
+            .filter(|t| !t.is_empty())
+            .cloned();
 
         contests.push(Contest {
             name: raw_name,
             start_time,
             url,
             platform: "HDU".to_string(),
+            end_time,
+            access,
+            ..Default::default()
         });
     }
 
-    // 5. 过滤与排序
-    let now = Utc::now();
+    // 6. 排序：按时间升序排列 (进行中的在最前面)
+    contests.sort_by_key(|c| c.start_time);
 
-    // 过滤掉已经结束的比赛 (只保留未来的)
-    contests.retain(|c| c.start_time > now);
+    if let Some(limit) = options.limit {
+        contests.truncate(limit);
+    }
 
-    // 关键修正：按时间升序排列 (即将开始的在最前面)
-    contests.sort_by(|a, b| a.start_time.cmp(&b.start_time));
+    Ok(contests)
+}
+
+// HDU 页面时间均为北京时间
+fn parse_hdu_time(text: &str) -> Option<DateTime<Utc>> {
+    time_parse::parse_datetime(text, time_parse::china(), Utc::now())
+}
+
+// [新增] 简要战绩由详细战绩汇总而来
+pub async fn fetch_user_stats(handle: &str) -> Result<UserStats> {
+    let profile = fetch_profile(handle).await?;
+
+    Ok(UserStats {
+        platform: "HDU".to_string(),
+        handle: profile.handle,
+        solved_count: profile.solved_count,
+        rank: profile.rank.map(|r| format!("Rank: {}", r)),
+        rating: None,
+    })
+}
+
+// [新增] 解析 userstatus.php
+// 统计表格形如 <tr><td>Problems Solved</td><td align=center>123</td></tr>
+// 题目列表由脚本输出: p(1000,1,2);p(1001,1,1); 参数依次为题号、AC 次数、提交次数
+pub async fn fetch_profile(handle: &str) -> Result<HduProfile> {
+    let client = reqwest::Client::builder()
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        .build()?;
+
+    let html = client
+        .get(HDU_USER_STATUS_URL)
+        .query(&[("user", handle)])
+        .send()
+        .await?
+        .text()
+        .await?;
 
-    // 只保留最近的 5 场
-    if contests.len() > 5 {
-        contests.truncate(5);
+    if html.contains("No such user") {
+        return Err(anyhow!("HDU user '{}' not found", handle));
     }
 
-    Ok(contests)
-}
\ No newline at end of file
+    let stat = |label: &str| -> Option<u32> {
+        let re = Regex::new(&format!(
+            r"(?i)<td>\s*{}\s*</td>\s*<td[^>]*>\s*(\d+)\s*</td>",
+            regex::escape(label)
+        ))
+        .unwrap();
+        re.captures(&html).and_then(|c| c[1].parse().ok())
+    };
+
+    let solved_count =
+        stat("Problems Solved").ok_or_else(|| anyhow!("HDU user status page parse failed"))?;
+
+    // 已通过与未通过的题目分别位于两个列表中，只取前者
+    let solved_section = match html.find("List of unsolved problems") {
+        Some(idx) => &html[..idx],
+        None => &html[..],
+    };
+    let re_problem = Regex::new(r"p\((\d+),\d+,\d+\);").unwrap();
+    let solved_problems = re_problem
+        .captures_iter(solved_section)
+        .filter_map(|c| c[1].parse().ok())
+        .collect();
+
+    Ok(HduProfile {
+        handle: handle.to_string(),
+        rank: stat("Rank"),
+        solved_count,
+        submitted_count: stat("Problems Submitted").unwrap_or(0),
+        submissions: stat("Submissions").unwrap_or(0),
+        accepted: stat("Accepted").unwrap_or(0),
+        solved_problems,
+    })
+}
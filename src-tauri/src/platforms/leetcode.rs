@@ -1,9 +1,14 @@
-use crate::models::{Contest, UserStats};
+use crate::models::{
+    Contest, DailyQuestion, DifficultyBreakdown, LeetCodeContestRanking, LeetCodeDaily,
+    LeetCodeProfile, LeetCodeRatingEntry, RatingEvent, RecentAcSubmission, UserStats,
+};
+use crate::time_parse;
 use anyhow::Result;
-use chrono::DateTime;
+use chrono::{DateTime, FixedOffset};
 use reqwest::Client;
 use serde::Deserialize;
 use serde_json::json;
+use std::collections::HashSet;
 
 // [新增] 国际服端点 (用于获取比赛列表，API 较稳定)
 const LEETCODE_COM_GRAPHQL_URL: &str = "https://leetcode.com/graphql";
@@ -25,11 +30,68 @@ struct Data {
     #[serde(rename = "upcomingContests")]
     upcoming_contests: Option<Vec<RawContest>>,
 
-    // 国服用户字段
+    // 用户字段 (两站通用)
     #[serde(rename = "userContestRanking")]
     user_contest_ranking: Option<UserContestRanking>,
+    #[serde(rename = "userContestRankingHistory")]
+    user_contest_ranking_history: Option<Vec<RankingHistoryItem>>,
+
+    // 国服用户字段
     #[serde(rename = "userProfileUserQuestionProgress")]
     user_question_progress: Option<UserQuestionProgress>,
+
+    #[serde(rename = "todayRecord")]
+    today_record: Option<Vec<TodayRecord>>,
+    #[serde(rename = "recentACSubmissions")]
+    cn_recent_ac: Option<Vec<CnRecentAc>>,
+
+    // 国际服用户字段
+    #[serde(rename = "matchedUser")]
+    matched_user: Option<MatchedUser>,
+    #[serde(rename = "activeDailyCodingChallengeQuestion")]
+    active_daily: Option<ActiveDaily>,
+    #[serde(rename = "recentAcSubmissionList")]
+    com_recent_ac: Option<Vec<ComRecentAc>>,
+}
+
+/// 用户所在站点：国服 (leetcode.cn) 与国际服 (leetcode.com) 的账号体系互不相通
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Region {
+    #[default]
+    Cn,
+    Com,
+}
+
+impl Region {
+    /// 解析前端传入的 region 字符串，缺省或无法识别时按国服处理 (兼容旧版本)
+    pub fn from_option(region: Option<&str>) -> Self {
+        match region.map(|r| r.trim().to_lowercase()).as_deref() {
+            Some("com") | Some("us") | Some("global") | Some("en") => Region::Com,
+            _ => Region::Cn,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Region::Cn => "cn",
+            Region::Com => "com",
+        }
+    }
+
+    fn domain(self) -> &'static str {
+        match self {
+            Region::Cn => "https://leetcode.cn",
+            Region::Com => "https://leetcode.com",
+        }
+    }
+
+    // 每日一题换题所用的时区：国服按北京时间，国际服按 UTC
+    fn daily_offset(self) -> FixedOffset {
+        match self {
+            Region::Cn => time_parse::china(),
+            Region::Com => FixedOffset::east_opt(0).unwrap(),
+        }
+    }
 }
 
 // --- 比赛相关 ---
@@ -46,6 +108,22 @@ struct RawContest {
 #[derive(Deserialize, Debug)]
 struct UserContestRanking {
     rating: f64,
+    #[serde(rename = "attendedContestsCount", default)]
+    attended_contests_count: u32,
+    #[serde(rename = "globalRanking", default)]
+    global_ranking: Option<u32>,
+    #[serde(rename = "topPercentage", default)]
+    top_percentage: Option<f64>,
+}
+
+// userContestRankingHistory 会列出注册以来的全部场次，attended = false 为未参加
+#[derive(Deserialize, Debug)]
+struct RankingHistoryItem {
+    attended: bool,
+    rating: f64,
+    #[serde(default)]
+    ranking: u32,
+    contest: RawContest,
 }
 
 #[derive(Deserialize, Debug)]
@@ -54,11 +132,82 @@ struct UserQuestionProgress {
     num_accepted_questions: Vec<QuestionCount>,
 }
 
+// 国服: difficulty 取值为 "EASY" / "MEDIUM" / "HARD"
 #[derive(Deserialize, Debug)]
 struct QuestionCount {
+    difficulty: String,
     count: u32,
 }
 
+// 国际服: matchedUser.submitStatsGlobal.acSubmissionNum
+#[derive(Deserialize, Debug)]
+struct MatchedUser {
+    #[serde(rename = "submitStatsGlobal")]
+    submit_stats_global: SubmitStats,
+}
+
+#[derive(Deserialize, Debug)]
+struct SubmitStats {
+    #[serde(rename = "acSubmissionNum")]
+    ac_submission_num: Vec<DifficultyCount>,
+}
+
+// 国际服: difficulty 取值为 "All" / "Easy" / "Medium" / "Hard"
+#[derive(Deserialize, Debug)]
+struct DifficultyCount {
+    difficulty: String,
+    count: u32,
+}
+
+// --- 每日一题相关 ---
+#[derive(Deserialize, Debug)]
+struct DailyQuestionInfo {
+    #[serde(rename = "questionFrontendId")]
+    question_frontend_id: String,
+    title: String,
+    #[serde(rename = "titleSlug")]
+    title_slug: String,
+    difficulty: String,
+}
+
+// 国服 todayRecord 返回数组，取第一项
+#[derive(Deserialize, Debug)]
+struct TodayRecord {
+    date: String,
+    question: DailyQuestionInfo,
+}
+
+#[derive(Deserialize, Debug)]
+struct ActiveDaily {
+    date: String,
+    question: DailyQuestionInfo,
+}
+
+#[derive(Deserialize, Debug)]
+struct CnRecentAc {
+    #[serde(rename = "submitTime")]
+    submit_time: i64,
+    question: CnRecentAcQuestion,
+}
+
+#[derive(Deserialize, Debug)]
+struct CnRecentAcQuestion {
+    title: String,
+    #[serde(rename = "translatedTitle")]
+    translated_title: Option<String>,
+    #[serde(rename = "titleSlug")]
+    title_slug: String,
+}
+
+// 国际服的 timestamp 为字符串形式的秒级时间戳
+#[derive(Deserialize, Debug)]
+struct ComRecentAc {
+    title: String,
+    #[serde(rename = "titleSlug")]
+    title_slug: String,
+    timestamp: String,
+}
+
 // [修改] 混合策略：从 COM 获取数据，但生成 CN 的链接
 pub async fn fetch_contests() -> Result<Vec<Contest>> {
     let client = Client::new();
@@ -103,13 +252,66 @@ pub async fn fetch_contests() -> Result<Vec<Contest>> {
             url: format!("https://leetcode.cn/contest/{}", raw.title_slug),
             platform: "LeetCode".to_string(), // 显示为 LeetCode (CN 逻辑由 URL 体现)
             start_time,
+            ..Default::default()
         });
     }
     Ok(contests)
 }
 
-// [保留] 查询 LeetCode CN 用户 Rating 和 刷题数 (保持不变，使用 CN 接口)
-pub async fn fetch_user_stats(handle: &str) -> Result<UserStats> {
+// [修改] 按账号所在站点分发，简要战绩由详细战绩汇总而来
+pub async fn fetch_user_stats(handle: &str, region: Region) -> Result<UserStats> {
+    let profile = fetch_profile(handle, region).await?;
+
+    Ok(UserStats {
+        platform: "LeetCode".to_string(),
+        handle: profile.handle,
+        solved_count: profile.solved.total,
+        rank: profile
+            .contest_ranking
+            .as_ref()
+            .and_then(|r| r.global_ranking)
+            .map(|r| format!("Global #{}", r)),
+        rating: profile.contest_ranking.map(|r| r.rating.round() as u32),
+    })
+}
+
+// [新增] 详细战绩：难度拆分 + 周赛排名 + Rating 历史
+pub async fn fetch_profile(handle: &str, region: Region) -> Result<LeetCodeProfile> {
+    match region {
+        Region::Cn => fetch_cn_profile(handle).await,
+        Region::Com => fetch_com_profile(handle).await,
+    }
+}
+
+// [新增] 统一格式的 Rating 历史，由详细战绩中的周赛记录转换
+// LeetCode 只给出每场结束后的 Rating，old_rating 取上一场的值
+pub async fn fetch_rating_history(handle: &str, region: Region) -> Result<Vec<RatingEvent>> {
+    let profile = fetch_profile(handle, region).await?;
+
+    let mut previous: Option<i32> = None;
+    Ok(profile
+        .rating_history
+        .into_iter()
+        .map(|entry| {
+            let new_rating = entry.rating.round() as i32;
+            let event = RatingEvent {
+                platform: "LeetCode".to_string(),
+                contest_id: entry.contest_slug,
+                contest_name: entry.contest_name,
+                time: Some(entry.start_time),
+                old_rating: previous,
+                new_rating,
+                rank: Some(entry.ranking),
+                performance: None,
+            };
+            previous = Some(new_rating);
+            event
+        })
+        .collect())
+}
+
+// [保留] 查询 LeetCode CN 用户 Rating 和 刷题数 (使用 CN 接口)
+async fn fetch_cn_profile(handle: &str) -> Result<LeetCodeProfile> {
     let client = Client::new();
     let profile_referer = format!("https://leetcode.cn/u/{}/", handle);
 
@@ -119,6 +321,19 @@ pub async fn fetch_user_stats(handle: &str) -> Result<UserStats> {
             query ($userSlug: String!) {
                 userContestRanking(userSlug: $userSlug) {
                     rating
+                    attendedContestsCount
+                    globalRanking
+                    topPercentage
+                }
+                userContestRankingHistory(userSlug: $userSlug) {
+                    attended
+                    rating
+                    ranking
+                    contest {
+                        title
+                        titleSlug
+                        startTime
+                    }
                 }
             }
         "#,
@@ -131,6 +346,7 @@ pub async fn fetch_user_stats(handle: &str) -> Result<UserStats> {
             query ($userSlug: String!) {
                 userProfileUserQuestionProgress(userSlug: $userSlug) {
                     numAcceptedQuestions {
+                        difficulty
                         count
                     }
                 }
@@ -157,43 +373,415 @@ pub async fn fetch_user_stats(handle: &str) -> Result<UserStats> {
             .send()
     );
 
-    // 4. 解析 Rating
-    let mut rating_val: Option<u32> = None;
+    // 4. 解析 Rating 与历史
+    let mut contest_ranking = None;
+    let mut rating_history = Vec::new();
     if let Ok(resp) = rating_resp {
         if resp.status().is_success() {
             if let Ok(g_resp) = resp.json::<GraphQlResponse>().await {
                 if let Some(d) = g_resp.data {
-                    if let Some(ranking) = d.user_contest_ranking {
-                        rating_val = Some(ranking.rating.round() as u32);
-                    }
+                    contest_ranking = d.user_contest_ranking.map(convert_ranking);
+                    rating_history =
+                        convert_history(d.user_contest_ranking_history.unwrap_or_default());
                 }
             }
         }
     }
 
     // 5. 解析 Solved Count
-    let mut solved_count: u32 = 0;
+    let mut solved = DifficultyBreakdown::default();
     if let Ok(resp) = solved_resp {
         if resp.status().is_success() {
             if let Ok(g_resp) = resp.json::<GraphQlResponse>().await {
                 if let Some(d) = g_resp.data {
                     if let Some(progress) = d.user_question_progress {
-                        solved_count = progress
-                            .num_accepted_questions
-                            .iter()
-                            .map(|q| q.count)
-                            .sum();
+                        for q in &progress.num_accepted_questions {
+                            add_difficulty_count(&mut solved, &q.difficulty, q.count);
+                        }
                     }
                 }
             }
         }
     }
 
-    Ok(UserStats {
-        platform: "LeetCode".to_string(),
+    Ok(LeetCodeProfile {
         handle: handle.to_string(),
-        solved_count,
-        rank: None,
-        rating: rating_val,
+        solved,
+        contest_ranking,
+        rating_history,
     })
-}
\ No newline at end of file
+}
+
+// [新增] 查询 LeetCode COM 用户 Rating 和 刷题数
+// 国际服一个 GraphQL 请求即可同时拿到全部数据
+async fn fetch_com_profile(handle: &str) -> Result<LeetCodeProfile> {
+    let client = Client::new();
+
+    let query = json!({
+        "query": r#"
+            query ($username: String!) {
+                matchedUser(username: $username) {
+                    submitStatsGlobal {
+                        acSubmissionNum {
+                            difficulty
+                            count
+                        }
+                    }
+                }
+                userContestRanking(username: $username) {
+                    rating
+                    attendedContestsCount
+                    globalRanking
+                    topPercentage
+                }
+                userContestRankingHistory(username: $username) {
+                    attended
+                    rating
+                    ranking
+                    contest {
+                        title
+                        titleSlug
+                        startTime
+                    }
+                }
+            }
+        "#,
+        "variables": { "username": handle }
+    });
+
+    let resp = client
+        .post(LEETCODE_COM_GRAPHQL_URL)
+        .header("Content-Type", "application/json")
+        .header("Origin", "https://leetcode.com")
+        .header("Referer", format!("https://leetcode.com/u/{}/", handle))
+        .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        .json(&query)
+        .send()
+        .await?;
+
+    let graphql_resp: GraphQlResponse = resp.json().await?;
+    let data = graphql_resp
+        .data
+        .ok_or_else(|| anyhow::anyhow!("LeetCode (.com) response missing 'data' field"))?;
+
+    // 用户不存在时 matchedUser 为 null
+    let matched_user = data
+        .matched_user
+        .ok_or_else(|| anyhow::anyhow!("LeetCode (.com) user '{}' not found", handle))?;
+
+    // acSubmissionNum 中 "All" 项即为总 AC 题数 (已按题目去重)，无需再求和
+    let mut solved = DifficultyBreakdown::default();
+    for q in &matched_user.submit_stats_global.ac_submission_num {
+        if q.difficulty.eq_ignore_ascii_case("All") {
+            continue;
+        }
+        add_difficulty_count(&mut solved, &q.difficulty, q.count);
+    }
+
+    // 未参加过周赛的用户 userContestRanking 为 null
+    Ok(LeetCodeProfile {
+        handle: handle.to_string(),
+        solved,
+        contest_ranking: data.user_contest_ranking.map(convert_ranking),
+        rating_history: convert_history(data.user_contest_ranking_history.unwrap_or_default()),
+    })
+}
+
+// ==================== 辅助函数 ====================
+
+fn add_difficulty_count(solved: &mut DifficultyBreakdown, difficulty: &str, count: u32) {
+    match difficulty.to_ascii_lowercase().as_str() {
+        "easy" => solved.easy += count,
+        "medium" => solved.medium += count,
+        "hard" => solved.hard += count,
+        _ => return,
+    }
+    solved.total += count;
+}
+
+fn convert_ranking(raw: UserContestRanking) -> LeetCodeContestRanking {
+    LeetCodeContestRanking {
+        rating: raw.rating,
+        attended_contests_count: raw.attended_contests_count,
+        global_ranking: raw.global_ranking,
+        top_percentage: raw.top_percentage,
+    }
+}
+
+fn convert_history(raw: Vec<RankingHistoryItem>) -> Vec<LeetCodeRatingEntry> {
+    let mut history: Vec<LeetCodeRatingEntry> = raw
+        .into_iter()
+        .filter(|item| item.attended)
+        .filter_map(|item| {
+            Some(LeetCodeRatingEntry {
+                start_time: DateTime::from_timestamp(item.contest.start_time, 0)?,
+                contest_name: item.contest.title,
+                contest_slug: item.contest.title_slug,
+                rating: item.rating,
+                ranking: item.ranking,
+            })
+        })
+        .collect();
+
+    history.sort_by_key(|entry| entry.start_time);
+    history
+}
+
+// ==================== 每日一题 ====================
+
+// [新增] 两站的每日一题 + 用户最近 AC
+// 是否已完成通过最近 AC 列表判断：当日题目若已通过必然位于列表前部，
+// 且只认站点当天 (daily 的日期) 的提交，以前做过同一题不算完成
+pub async fn fetch_daily(handle: Option<&str>, region: Region) -> Result<LeetCodeDaily> {
+    let client = Client::new();
+
+    let recent_future = async {
+        match handle {
+            Some(h) if !h.trim().is_empty() => fetch_recent_ac(&client, h.trim(), region).await,
+            _ => Ok(Vec::new()),
+        }
+    };
+
+    let (cn_res, com_res, recent_res) = tokio::join!(
+        fetch_daily_question(&client, Region::Cn),
+        fetch_daily_question(&client, Region::Com),
+        recent_future
+    );
+
+    // 单个站点失败不影响另一站点的展示；最近 AC 获取失败时完成状态未知 (None)
+    let recent_ac = recent_res.ok();
+    let has_handle = handle.is_some_and(|h| !h.trim().is_empty());
+
+    let mut questions = Vec::new();
+    for (mut question, question_region, slug) in [cn_res, com_res].into_iter().flatten() {
+        if has_handle && question_region == region {
+            if let Some(recent) = &recent_ac {
+                question.solved = Some(solved_on_date(recent, &slug, &question.date, region));
+            }
+        }
+        questions.push(question);
+    }
+
+    if questions.is_empty() {
+        return Err(anyhow::anyhow!("Failed to fetch LeetCode daily question"));
+    }
+
+    Ok(LeetCodeDaily {
+        questions,
+        recent_ac: recent_ac.unwrap_or_default(),
+    })
+}
+
+// 是否在 date (站点当天，"YYYY-MM-DD") 通过了该题
+fn solved_on_date(
+    recent_ac: &[RecentAcSubmission],
+    slug: &str,
+    date: &str,
+    region: Region,
+) -> bool {
+    let offset = region.daily_offset();
+    recent_ac.iter().any(|s| {
+        s.title_slug == slug && s.time.with_timezone(&offset).format("%Y-%m-%d").to_string() == date
+    })
+}
+
+async fn post_graphql(client: &Client, region: Region, query: &serde_json::Value) -> Result<Data> {
+    let url = match region {
+        Region::Cn => LEETCODE_CN_GRAPHQL_URL,
+        Region::Com => LEETCODE_COM_GRAPHQL_URL,
+    };
+
+    let resp = client
+        .post(url)
+        .header("Content-Type", "application/json")
+        .header("Origin", region.domain())
+        .header("Referer", format!("{}/problemset/", region.domain()))
+        .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        .json(query)
+        .send()
+        .await?;
+
+    let graphql_resp: GraphQlResponse = resp.json().await?;
+    graphql_resp.data.ok_or_else(|| {
+        anyhow::anyhow!(
+            "LeetCode (.{}) response missing 'data' field",
+            region.as_str()
+        )
+    })
+}
+
+// 返回 (题目, 所属站点, titleSlug)，slug 用于与最近 AC 比对
+async fn fetch_daily_question(
+    client: &Client,
+    region: Region,
+) -> Result<(DailyQuestion, Region, String)> {
+    let (date, info) = match region {
+        Region::Cn => {
+            let query = json!({
+                "query": r#"
+                    query questionOfToday {
+                        todayRecord {
+                            date
+                            question {
+                                questionFrontendId
+                                title
+                                titleSlug
+                                difficulty
+                            }
+                        }
+                    }
+                "#
+            });
+            let record = post_graphql(client, region, &query)
+                .await?
+                .today_record
+                .and_then(|records| records.into_iter().next())
+                .ok_or_else(|| anyhow::anyhow!("LeetCode (.cn) todayRecord is empty"))?;
+            (record.date, record.question)
+        }
+        Region::Com => {
+            let query = json!({
+                "query": r#"
+                    query questionOfToday {
+                        activeDailyCodingChallengeQuestion {
+                            date
+                            question {
+                                questionFrontendId
+                                title
+                                titleSlug
+                                difficulty
+                            }
+                        }
+                    }
+                "#
+            });
+            let daily = post_graphql(client, region, &query)
+                .await?
+                .active_daily
+                .ok_or_else(|| anyhow::anyhow!("LeetCode (.com) daily question is empty"))?;
+            (daily.date, daily.question)
+        }
+    };
+
+    let question = DailyQuestion {
+        region: region.as_str().to_string(),
+        date,
+        frontend_id: info.question_frontend_id,
+        title: info.title,
+        difficulty: normalize_difficulty(&info.difficulty),
+        url: format!("{}/problems/{}/", region.domain(), info.title_slug),
+        solved: None,
+    };
+
+    Ok((question, region, info.title_slug))
+}
+
+async fn fetch_recent_ac(
+    client: &Client,
+    handle: &str,
+    region: Region,
+) -> Result<Vec<RecentAcSubmission>> {
+    let mut submissions = Vec::new();
+
+    match region {
+        Region::Cn => {
+            // 国服的 recentACSubmissions 位于 noj-go 端点
+            let query = json!({
+                "query": r#"
+                    query ($userSlug: String!) {
+                        recentACSubmissions(userSlug: $userSlug) {
+                            submitTime
+                            question {
+                                title
+                                translatedTitle
+                                titleSlug
+                            }
+                        }
+                    }
+                "#,
+                "variables": { "userSlug": handle }
+            });
+
+            let resp = client
+                .post(LEETCODE_CN_NOJ_URL)
+                .header("Content-Type", "application/json")
+                .header("Origin", "https://leetcode.cn")
+                .header("Referer", format!("https://leetcode.cn/u/{}/", handle))
+                .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36")
+                .json(&query)
+                .send()
+                .await?;
+
+            let graphql_resp: GraphQlResponse = resp.json().await?;
+            let raw_list = graphql_resp
+                .data
+                .and_then(|d| d.cn_recent_ac)
+                .unwrap_or_default();
+
+            for raw in raw_list {
+                let Some(time) = DateTime::from_timestamp(raw.submit_time, 0) else {
+                    continue;
+                };
+                submissions.push(RecentAcSubmission {
+                    url: format!("https://leetcode.cn/problems/{}/", raw.question.title_slug),
+                    title: raw.question.translated_title.unwrap_or(raw.question.title),
+                    title_slug: raw.question.title_slug,
+                    time,
+                });
+            }
+        }
+        Region::Com => {
+            let query = json!({
+                "query": r#"
+                    query ($username: String!, $limit: Int!) {
+                        recentAcSubmissionList(username: $username, limit: $limit) {
+                            title
+                            titleSlug
+                            timestamp
+                        }
+                    }
+                "#,
+                "variables": { "username": handle, "limit": 20 }
+            });
+
+            let raw_list = post_graphql(client, region, &query)
+                .await?
+                .com_recent_ac
+                .unwrap_or_default();
+
+            for raw in raw_list {
+                let Some(time) = raw
+                    .timestamp
+                    .parse::<i64>()
+                    .ok()
+                    .and_then(|ts| DateTime::from_timestamp(ts, 0))
+                else {
+                    continue;
+                };
+                submissions.push(RecentAcSubmission {
+                    url: format!("https://leetcode.com/problems/{}/", raw.title_slug),
+                    title: raw.title,
+                    title_slug: raw.title_slug,
+                    time,
+                });
+            }
+        }
+    }
+
+    // 同一道题可能多次 AC，只保留最近一次
+    submissions.sort_by_key(|s| std::cmp::Reverse(s.time));
+    let mut seen = HashSet::new();
+    submissions.retain(|s| seen.insert(s.title_slug.clone()));
+
+    Ok(submissions)
+}
+
+// 国服返回 "EASY"，国际服返回 "Easy"，统一为后者
+fn normalize_difficulty(raw: &str) -> String {
+    let lower = raw.to_lowercase();
+    let mut chars = lower.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,54 @@
+use crate::models::UserStats;
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use serde_json::json;
+
+// 新版 LibreOJ (loj.ac) 前后端分离，用户信息走 JSON API
+// 新版没有比赛模块，因此只提供用户统计
+const LOJ_API_URL: &str = "https://api.loj.ac/api";
+
+#[derive(Deserialize)]
+struct LojUserMetaResponse {
+    #[serde(default)]
+    meta: Option<LojUserMeta>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LojUserMeta {
+    username: String,
+    accepted_problem_count: u32,
+    #[serde(default)]
+    rating: Option<i32>,
+}
+
+pub async fn fetch_user_stats(handle: &str) -> Result<UserStats> {
+    let client = reqwest::Client::new();
+
+    let resp: LojUserMetaResponse = client
+        .post(format!("{}/user/getUserMeta", LOJ_API_URL))
+        .json(&json!({ "username": handle, "getPrivileges": false }))
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    let meta = resp.meta.ok_or_else(|| {
+        anyhow!(
+            "LibreOJ user '{}' not found: {}",
+            handle,
+            resp.error.unwrap_or_default()
+        )
+    })?;
+
+    Ok(UserStats {
+        platform: "LibreOJ".to_string(),
+        handle: meta.username,
+        solved_count: meta.accepted_problem_count,
+        rank: None,
+        // 未参加过比赛的用户 Rating 为默认值 0
+        rating: meta.rating.filter(|&r| r > 0).map(|r| r as u32),
+    })
+}
@@ -1,9 +1,14 @@
-use crate::models::{Contest, UserStats};
+use crate::models::{
+    Contest, LuoguContestOptions, LuoguDifficultyBucket, LuoguProfile, RatedRange, RatingEvent,
+    UserStats,
+};
+use crate::session::AntiBotSession;
 use anyhow::{anyhow, Result}; // [修改] 使用 anyhow 处理错误
 use chrono::{Duration, TimeZone, Utc};
 use regex::Regex;
-use reqwest::{header, Client, Response};
+use reqwest::header::{self, HeaderMap, HeaderValue};
 use serde::Deserialize;
+use std::sync::OnceLock;
 
 // ==================== 1. 结构体定义 ====================
 
@@ -23,6 +28,9 @@ struct CurrentData {
     user: Option<UserInfo>,
     #[serde(default)]
     elo: Vec<EloStats>,
+    // 隐藏练习记录的用户只对本人 (携带登录 Cookie) 返回该字段
+    #[serde(rename = "passedProblems", default)]
+    passed_problems: Option<Vec<LuoguProblem>>,
 
     // 比赛查询用
     #[serde(default)]
@@ -32,6 +40,8 @@ struct CurrentData {
 // --- 用户战绩相关 ---
 #[derive(Debug, Deserialize)]
 struct UserInfo {
+    #[serde(default)]
+    name: Option<String>,
     #[serde(rename = "passedProblemCount")]
     passed_problem_count: i32,
     #[serde(default)]
@@ -43,10 +53,66 @@ struct EloStats {
     rating: i32,
 }
 
+#[derive(Debug, Deserialize)]
+struct LuoguProblem {
+    pid: String,
+    // 0 为暂无评定
+    #[serde(default)]
+    difficulty: u8,
+}
+
+// 洛谷题目难度: (名称, 颜色)，下标即 difficulty 值
+const DIFFICULTY_LEVELS: [(&str, &str); 8] = [
+    ("暂无评定", "#bfbfbf"),
+    ("入门", "#fe4c61"),
+    ("普及−", "#f39c11"),
+    ("普及/提高−", "#ffc116"),
+    ("普及+/提高", "#52c41a"),
+    ("提高+/省选−", "#3498db"),
+    ("省选/NOI−", "#9d3dcf"),
+    ("NOI/NOI+/CTSC", "#0e1d69"),
+];
+
+// --- Rating 历史相关 (/api/rating/elo) ---
+#[derive(Debug, Deserialize)]
+struct EloHistoryResponse {
+    records: EloHistoryPage,
+}
+
+#[derive(Debug, Deserialize)]
+struct EloHistoryPage {
+    #[serde(default)]
+    result: Vec<EloRecord>,
+    #[serde(default)]
+    count: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct EloRecord {
+    rating: i32,
+    #[serde(default)]
+    time: Option<i64>,
+    #[serde(default)]
+    contest: Option<EloContest>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EloContest {
+    id: i64,
+    name: String,
+    #[serde(rename = "endTime", default)]
+    end_time: Option<i64>,
+}
+
+// Rating 历史最多翻的页数
+const ELO_HISTORY_MAX_PAGES: u32 = 10;
+
 // --- 比赛列表相关 ---
 #[derive(Debug, Deserialize)]
 struct ContestResult {
     result: Vec<LuoguRawContest>,
+    #[serde(default)]
+    count: usize,
 }
 
 #[derive(Debug, Deserialize)]
@@ -56,110 +122,188 @@ struct LuoguRawContest {
     #[serde(rename = "startTime")]
     start_time: i64,
     #[serde(rename = "endTime")]
-    #[allow(dead_code)] // 消除 unused warning
     end_time: i64,
+    #[serde(default)]
+    rated: bool,
+    #[serde(rename = "ruleType", default)]
+    rule_type: i32,
+    #[serde(rename = "visibilityType", default)]
+    visibility_type: i32,
+    #[serde(default)]
+    host: Option<LuoguHost>,
+}
+
+// 主办方可能是用户 (uid) 或团队 (id)，这里只关心名称
+#[derive(Debug, Deserialize)]
+struct LuoguHost {
+    #[serde(default)]
+    name: String,
+}
+
+// 洛谷官方比赛的 visibilityType
+const VISIBILITY_OFFICIAL: i32 = 1;
+
+// 比赛时间窗口上限 (天)，设置超出时按上限处理
+const MAX_WINDOW_DAYS: i64 = 365;
+
+// 比赛列表最多翻的页数
+const CONTEST_LIST_MAX_PAGES: u32 = 5;
+
+fn rule_type_name(rule_type: i32) -> Option<&'static str> {
+    match rule_type {
+        1 => Some("OI"),
+        2 => Some("ACM"),
+        3 => Some("乐多"),
+        4 => Some("IOI"),
+        5 => Some("Codeforces"),
+        _ => None,
+    }
+}
+
+fn visibility_type_name(visibility_type: i32) -> Option<&'static str> {
+    match visibility_type {
+        1 => Some("官方比赛"),
+        2 => Some("团队公开赛"),
+        3 => Some("团队内部赛"),
+        4 => Some("个人公开赛"),
+        5 => Some("个人邀请赛"),
+        6 => Some("团队邀请赛"),
+        _ => None,
+    }
 }
 
 // ==================== 2. 核心网络辅助函数 (WAF 处理器) ====================
 
-/// 通用的请求函数，封装了 Client 构建、Header 伪装、WAF Cookie 重试逻辑
-/// [修改] 返回类型改为 anyhow::Result<String>
-async fn fetch_raw_content(url: &str) -> Result<String> {
-    let user_agent = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
-    let referer = url;
-
-    let client = Client::builder()
-        .redirect(reqwest::redirect::Policy::none())
-        .build()?;
-
-    let mut resp: Response = client
-        .get(url)
-        .header(header::USER_AGENT, user_agent)
-        .header(header::ACCEPT, "application/json, text/plain, */*")
-        .header("x-luogu-type", "content-only")
-        .header(header::REFERER, referer)
-        .send()
-        .await?;
-
-    // 处理 WAF 重定向 (302/307)
-    if resp.status().is_redirection() {
-        let cookies: Vec<String> = resp
-            .headers()
-            .get_all(header::SET_COOKIE)
-            .iter()
-            .filter_map(|h| h.to_str().ok())
-            .map(|s| s.split(';').next().unwrap_or("").to_string())
-            .collect();
-
-        if !cookies.is_empty() {
-            let cookie_str = cookies.join("; ");
-            let location = resp
-                .headers()
-                .get(header::LOCATION)
-                .and_then(|h| h.to_str().ok())
-                .unwrap_or(url);
-
-            // 携带 Cookie 重试
-            resp = client
-                .get(location)
-                .header(header::USER_AGENT, user_agent)
-                .header(header::ACCEPT, "application/json, text/plain, */*")
-                .header("x-luogu-type", "content-only")
-                .header(header::COOKIE, cookie_str)
-                .send()
-                .await?;
-        }
+// 洛谷 WAF 质询页面的特征 (脚本写入 C3VK Cookie 后刷新)
+const LUOGU_CHALLENGE_MARKERS: &[&str] = &["C3VK="];
+
+/// 全局共享的洛谷会话，质询 Cookie 在请求之间与重启之间复用
+/// 创建失败 (HTTP 客户端无法初始化) 时返回错误，下次请求再重试
+fn luogu_session() -> Result<&'static AntiBotSession> {
+    static SESSION: OnceLock<AntiBotSession> = OnceLock::new();
+    if let Some(session) = SESSION.get() {
+        return Ok(session);
+    }
+    let session = AntiBotSession::new("luogu", LUOGU_CHALLENGE_MARKERS)?;
+    Ok(SESSION.get_or_init(|| session))
+}
+
+/// 通用的请求函数，封装了 Header 伪装，WAF 质询交给 AntiBotSession 处理
+/// [修改] 可选携带用户登录 Cookie (`__client_id` / `_uid`)
+/// 质询未能通过时返回 `session::BlockedError`
+async fn fetch_raw_content(url: &str, user_cookie: Option<&str>) -> Result<String> {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::ACCEPT,
+        HeaderValue::from_static("application/json, text/plain, */*"),
+    );
+    headers.insert("x-luogu-type", HeaderValue::from_static("content-only"));
+    if let Ok(referer) = HeaderValue::from_str(url) {
+        headers.insert(header::REFERER, referer);
     }
 
-    let text = resp.text().await?;
-    Ok(text)
+    luogu_session()?.get_text(url, headers, user_cookie).await
 }
 
 // ==================== 3. 业务功能实现 ====================
 
-// [修改] 返回类型改为 anyhow::Result
-pub async fn fetch_contests() -> Result<Vec<Contest>> {
-    let url = "https://www.luogu.com.cn/contest/list?_contentOnly=1";
-    let raw_text = fetch_raw_content(url).await?;
-
-    // 尝试解析 JSON
-    let luogu_resp: LuoguResponse = serde_json::from_str(&raw_text)
-        .map_err(|e| anyhow!("Failed to parse Luogu contest list: {}", e))?;
+// [修改] 时间窗口与官方/Rated 过滤由调用方指定
+// 列表按开始时间倒序分页，一直翻到出现已开始的比赛，避免窗口内的比赛落在后面的页上
+pub async fn fetch_contests(options: &LuoguContestOptions) -> Result<Vec<Contest>> {
+    let now = Utc::now();
+    let window_end = now + Duration::days(options.window_days.clamp(1, MAX_WINDOW_DAYS));
+    let mut raw_contests: Vec<LuoguRawContest> = Vec::new();
+
+    for page in 1..=CONTEST_LIST_MAX_PAGES {
+        let url = format!(
+            "https://www.luogu.com.cn/contest/list?page={}&_contentOnly=1",
+            page
+        );
+        let raw_text = fetch_raw_content(&url, None).await?;
+
+        // 尝试解析 JSON
+        let luogu_resp: LuoguResponse = serde_json::from_str(&raw_text)
+            .map_err(|e| anyhow!("Failed to parse Luogu contest list: {}", e))?;
+
+        if luogu_resp.code != 200 {
+            return Err(anyhow!("Luogu API error code: {}", luogu_resp.code));
+        }
 
-    if luogu_resp.code != 200 {
-        return Err(anyhow!("Luogu API error code: {}", luogu_resp.code));
+        let Some(data) = luogu_resp.current_data.contests else {
+            break;
+        };
+        if data.result.is_empty() {
+            break;
+        }
+        let reached_started = data
+            .result
+            .iter()
+            .any(|raw| raw.start_time <= now.timestamp());
+        raw_contests.extend(data.result);
+        if reached_started || (data.count > 0 && raw_contests.len() >= data.count) {
+            break;
+        }
     }
 
     let mut contests = Vec::new();
 
-    if let Some(data) = luogu_resp.current_data.contests {
-        let now = Utc::now();
-        let two_weeks_later = now + Duration::days(14);
-
-        for raw in data.result {
-            let start_time = Utc.timestamp_opt(raw.start_time, 0).single().unwrap_or(now);
-
-            // 筛选条件：开始时间在未来且在两周内
-            if start_time > now && start_time < two_weeks_later {
-                contests.push(Contest {
-                    platform: "Luogu".to_string(),
-                    name: raw.name,
-                    url: format!("https://www.luogu.com.cn/contest/{}", raw.id),
-                    start_time,
-                });
-            }
+    for raw in raw_contests {
+        let start_time = Utc.timestamp_opt(raw.start_time, 0).single().unwrap_or(now);
+
+        // 筛选条件：开始时间在未来且在时间窗口内
+        if start_time <= now || start_time >= window_end {
+            continue;
+        }
+        if options.official_only && raw.visibility_type != VISIBILITY_OFFICIAL {
+            continue;
+        }
+        if options.rated_only && !raw.rated {
+            continue;
         }
+
+        contests.push(Contest {
+            platform: "Luogu".to_string(),
+            name: raw.name,
+            url: format!("https://www.luogu.com.cn/contest/{}", raw.id),
+            start_time,
+            end_time: Utc.timestamp_opt(raw.end_time, 0).single(),
+            // 洛谷 Rated 比赛不区分分段
+            rated_range: raw.rated.then(RatedRange::default),
+            format: rule_type_name(raw.rule_type).map(str::to_string),
+            category: visibility_type_name(raw.visibility_type).map(str::to_string),
+            host: raw.host.map(|h| h.name).filter(|name| !name.is_empty()),
+            ..Default::default()
+        });
     }
 
-    contests.sort_by(|a, b| a.start_time.cmp(&b.start_time));
+    contests.sort_by_key(|c| c.start_time);
 
     Ok(contests)
 }
 
-// [修改] 返回类型改为 anyhow::Result
-pub async fn fetch_user_stats(uid: &str) -> Result<UserStats> {
+// [修改] 简要战绩由详细战绩汇总而来
+pub async fn fetch_user_stats(uid: &str, cookie: Option<&str>) -> Result<UserStats> {
+    let profile = fetch_profile(uid, cookie).await?;
+
+    Ok(UserStats {
+        platform: "Luogu".to_string(),
+        handle: profile.uid,
+        rating: profile.rating,
+        rank: profile.ranking.map(|r| r.to_string()),
+        solved_count: profile.solved_count,
+    })
+}
+
+// [新增] 详细战绩；携带本人 Cookie 时可读取隐藏的练习记录
+pub async fn fetch_profile(uid: &str, cookie: Option<&str>) -> Result<LuoguProfile> {
+    let cookie = cookie.and_then(clean_cookie);
+    let authenticated = cookie
+        .as_deref()
+        .and_then(cookie_uid)
+        .is_some_and(|cookie_uid| cookie_uid == uid);
+
     let url = format!("https://www.luogu.com.cn/user/{}?_contentOnly=1", uid);
-    let raw_text = fetch_raw_content(&url).await?;
+    let raw_text = fetch_raw_content(&url, cookie.as_deref()).await?;
 
     // === 逻辑分支 1: HTML Fallback ===
     if raw_text.trim().starts_with('<') {
@@ -175,12 +319,15 @@ pub async fn fetch_user_stats(uid: &str) -> Result<UserStats> {
             .and_then(|c| c[1].parse().ok());
 
         if passed_count > 0 {
-            return Ok(UserStats {
-                platform: "Luogu".to_string(),
-                handle: uid.to_string(),
-                rating: elo_rating,
-                rank: None,
+            return Ok(LuoguProfile {
+                uid: uid.to_string(),
+                name: None,
                 solved_count: passed_count,
+                rating: elo_rating,
+                ranking: None,
+                passed_problems: None,
+                difficulty_distribution: None,
+                authenticated,
             });
         }
         return Err(anyhow!("Luogu HTML parse failed"));
@@ -196,18 +343,127 @@ pub async fn fetch_user_stats(uid: &str) -> Result<UserStats> {
         ));
     }
 
-    let user = luogu_data
-        .current_data
+    let current_data = luogu_data.current_data;
+    let user = current_data
         .user
         .ok_or_else(|| anyhow!("Missing user data"))?;
 
-    let rating = luogu_data.current_data.elo.first().map(|e| e.rating as u32);
+    let rating = current_data.elo.first().map(|e| e.rating as u32);
+    let difficulty_distribution = current_data
+        .passed_problems
+        .as_deref()
+        .map(build_difficulty_distribution);
 
-    Ok(UserStats {
-        platform: "Luogu".to_string(),
-        handle: uid.to_string(),
-        rating,
-        rank: user.ranking.map(|r| r.to_string()),
+    Ok(LuoguProfile {
+        uid: uid.to_string(),
+        name: user.name,
         solved_count: user.passed_problem_count as u32,
+        rating,
+        ranking: user.ranking.map(|r| r as u32),
+        passed_problems: current_data
+            .passed_problems
+            .map(|problems| problems.into_iter().map(|p| p.pid).collect()),
+        difficulty_distribution,
+        authenticated,
     })
 }
+
+// [新增] 统一格式的 Rating 历史；接口按时间倒序分页返回
+pub async fn fetch_rating_history(uid: &str, cookie: Option<&str>) -> Result<Vec<RatingEvent>> {
+    let cookie = cookie.and_then(clean_cookie);
+    let mut records: Vec<EloRecord> = Vec::new();
+
+    for page in 1..=ELO_HISTORY_MAX_PAGES {
+        let url = format!(
+            "https://www.luogu.com.cn/api/rating/elo?user={}&page={}",
+            uid, page
+        );
+        let raw_text = fetch_raw_content(&url, cookie.as_deref()).await?;
+        let resp: EloHistoryResponse = serde_json::from_str(&raw_text)
+            .map_err(|e| anyhow!("Failed to parse Luogu rating history: {}", e))?;
+
+        let total = resp.records.count;
+        if resp.records.result.is_empty() {
+            break;
+        }
+        records.extend(resp.records.result);
+        if records.len() >= total {
+            break;
+        }
+    }
+
+    let mut events: Vec<RatingEvent> = records
+        .into_iter()
+        .map(|record| {
+            let time = record
+                .time
+                .or_else(|| record.contest.as_ref().and_then(|c| c.end_time))
+                .and_then(|ts| Utc.timestamp_opt(ts, 0).single());
+            RatingEvent {
+                platform: "Luogu".to_string(),
+                contest_id: record
+                    .contest
+                    .as_ref()
+                    .map(|c| c.id.to_string())
+                    .unwrap_or_default(),
+                contest_name: record.contest.map(|c| c.name).unwrap_or_default(),
+                time,
+                old_rating: None,
+                new_rating: record.rating,
+                rank: None,
+                performance: None,
+            }
+        })
+        .collect();
+
+    // 接口只给出每场结束后的 Rating，old_rating 取上一场的值
+    events.sort_by_key(|e| e.time);
+    for i in 1..events.len() {
+        events[i].old_rating = Some(events[i - 1].new_rating);
+    }
+
+    Ok(events)
+}
+
+/// 按难度分桶，始终返回全部 8 档 (方便前端画固定配色的柱状图)
+fn build_difficulty_distribution(problems: &[LuoguProblem]) -> Vec<LuoguDifficultyBucket> {
+    let mut buckets: Vec<LuoguDifficultyBucket> = DIFFICULTY_LEVELS
+        .iter()
+        .enumerate()
+        .map(|(level, (name, color))| LuoguDifficultyBucket {
+            level: level as u8,
+            name: name.to_string(),
+            color: color.to_string(),
+            count: 0,
+            problems: Vec::new(),
+        })
+        .collect();
+
+    for problem in problems {
+        // 未知的难度值归入 "暂无评定"
+        let level = problem.difficulty as usize;
+        let level = if level < buckets.len() { level } else { 0 };
+        let bucket = &mut buckets[level];
+        bucket.count += 1;
+        bucket.problems.push(problem.pid.clone());
+    }
+
+    buckets
+}
+
+// ==================== 4. Cookie 辅助函数 ====================
+
+/// 清理用户粘贴的 Cookie 中的换行符，空串视为未配置
+fn clean_cookie(raw: &str) -> Option<String> {
+    let cleaned = raw.replace(['\n', '\r'], "").trim().to_string();
+    (!cleaned.is_empty()).then_some(cleaned)
+}
+
+/// 从 Cookie 中取出 `_uid`，用于判断是否在查询本人
+fn cookie_uid(cookie: &str) -> Option<&str> {
+    cookie
+        .split(';')
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == "_uid")
+        .map(|(_, value)| value.trim())
+}
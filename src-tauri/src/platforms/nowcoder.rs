@@ -1,14 +1,27 @@
-use crate::models::{Contest, UserStats};
+use crate::models::{
+    Contest, NowCoderContestOptions, NowCoderSession, RatedRange, RatingEvent, UserStats,
+};
+use crate::session::SessionExpiredError;
+use crate::time_parse;
 use anyhow::Result;
-use chrono::{Datelike, FixedOffset, Local, NaiveDateTime, TimeZone, Utc};
+use chrono::{DateTime, Duration, Utc};
 use regex::Regex;
 use reqwest::header::{
-    HeaderMap, HeaderValue, ACCEPT, ACCEPT_LANGUAGE, COOKIE, REFERER, USER_AGENT,
+    HeaderMap, HeaderValue, ACCEPT, ACCEPT_LANGUAGE, COOKIE, LOCATION, REFERER, SET_COOKIE,
+    USER_AGENT,
 };
-use reqwest::Client;
+use reqwest::{Client, Response};
 use scraper::{Html, Selector};
+use serde::Deserialize;
+use tokio::task::JoinSet;
+
+const NOWCODER_CONTEST_LIST_URL: &str = "https://ac.nowcoder.com/acm/contest/vip-index";
+
+// 比赛列表页顶部的分类标签 (topCategoryFilter)
+const CONTEST_CATEGORIES: [(u32, &str); 3] = [(13, "官方"), (14, "高校"), (15, "个人")];
 
-pub async fn fetch_contests() -> Result<Vec<Contest>> {
+// [修改] 抓取全部分类的前若干页，并标注分类、结束时间与 Rated
+pub async fn fetch_contests(options: &NowCoderContestOptions) -> Result<Vec<Contest>> {
     let mut headers = HeaderMap::new();
     headers.insert(USER_AGENT, HeaderValue::from_static("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/121.0.0.0 Safari/537.36"));
     headers.insert(ACCEPT, HeaderValue::from_static("text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8"));
@@ -23,11 +36,85 @@ pub async fn fetch_contests() -> Result<Vec<Contest>> {
 
     let client = Client::builder().default_headers(headers).build()?;
 
-    let url = "https://ac.nowcoder.com/acm/contest/vip-index";
-    let resp = client.get(url).send().await?;
+    // 各分类并发抓取，分类内逐页抓取直到空页
+    // 首页失败的分类视为失败；后续页失败时保留已抓到的部分
+    let mut tasks = JoinSet::new();
+    for (filter, label) in CONTEST_CATEGORIES {
+        let client = client.clone();
+        let max_pages = options.max_pages;
+        tasks.spawn(async move {
+            let mut contests = Vec::new();
+            for page in 1..=max_pages {
+                match fetch_contest_page(&client, filter, page, label).await {
+                    Ok(page_contests) if !page_contests.is_empty() => {
+                        contests.extend(page_contests)
+                    }
+                    Err(e) if page == 1 => return Err(e),
+                    _ => break,
+                }
+            }
+            Ok(contests)
+        });
+    }
+
+    let mut contests: Vec<Contest> = Vec::new();
+    let mut last_error = None;
+    let mut any_ok = false;
+    while let Some(res) = tasks.join_next().await {
+        let category_contests = match res {
+            Ok(Ok(category_contests)) => category_contests,
+            Ok(Err(e)) => {
+                last_error = Some(e);
+                continue;
+            }
+            Err(e) => {
+                last_error = Some(e.into());
+                continue;
+            }
+        };
+        any_ok = true;
+        for contest in category_contests {
+            // 同一场比赛可能出现在多个分类中
+            if !contests.iter().any(|c| c.url == contest.url) {
+                contests.push(contest);
+            }
+        }
+    }
+
+    // 所有分类都失败时返回错误，以免被当作 "没有比赛" (误判取消、跳过 clist 兜底)
+    if !any_ok {
+        if let Some(e) = last_error {
+            return Err(e);
+        }
+    }
+
+    let now = Utc::now();
+    contests.retain(|c| c.end_time.unwrap_or(c.start_time) > now);
+    contests.sort_by_key(|c| c.start_time);
+
+    Ok(contests)
+}
+
+async fn fetch_contest_page(
+    client: &Client,
+    category_filter: u32,
+    page: u32,
+    category_label: &str,
+) -> Result<Vec<Contest>> {
+    let resp = client
+        .get(NOWCODER_CONTEST_LIST_URL)
+        .query(&[
+            ("topCategoryFilter", category_filter.to_string()),
+            ("page", page.to_string()),
+        ])
+        .send()
+        .await?;
 
     if !resp.status().is_success() {
-        return Ok(vec![]);
+        return Err(anyhow::anyhow!(
+            "NowCoder contest list returned status {}",
+            resp.status()
+        ));
     }
 
     let html_text = resp.text().await?;
@@ -58,28 +145,35 @@ pub async fn fetch_contests() -> Result<Vec<Contest>> {
         let raw_text = element.text().collect::<Vec<_>>().join(" ");
 
         if let (Some(start_time), true) = parse_nowcoder_time(&raw_text) {
-            if start_time > Utc::now() {
-                contests.push(Contest {
-                    name,
-                    start_time,
-                    url: full_url,
-                    platform: "NowCoder".to_string(),
-                });
-            }
+            // 列表中的 Rated 标签；"不计rating" 之类的说明不算
+            let rated = raw_text.contains("Rated")
+                && !raw_text.contains("Unrated")
+                && !raw_text.contains("不计");
+
+            contests.push(Contest {
+                name,
+                start_time,
+                url: full_url,
+                platform: "NowCoder".to_string(),
+                end_time: parse_nowcoder_end_time(&raw_text),
+                rated_range: rated.then(RatedRange::default),
+                category: Some(category_label.to_string()),
+                ..Default::default()
+            });
         }
     }
 
     Ok(contests)
 }
 
+// 未登录访问个人页会被重定向到该入口
+const NOWCODER_PROFILE_INDEX: &str = "https://ac.nowcoder.com/acm/contest/profile-index";
+
 // [正式版] 已移除调试日志，保留 Regex 解析逻辑
+// [修改] Cookie 失效时返回 SessionExpiredError，而不是静默返回 0
 pub async fn fetch_user_stats(uid: &str, cookie: &str) -> Result<UserStats> {
     // 清理 Cookie 中的换行符，防止 Header 报错
-    let clean_cookie = cookie
-        .replace('\n', "")
-        .replace('\r', "")
-        .trim()
-        .to_string();
+    let clean_cookie = clean_cookie(cookie);
 
     if clean_cookie.is_empty() {
         return Err(anyhow::anyhow!("Cookie is empty"));
@@ -103,7 +197,7 @@ pub async fn fetch_user_stats(uid: &str, cookie: &str) -> Result<UserStats> {
             ACCEPT,
             "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,*/*;q=0.8",
         )
-        .header(REFERER, "https://ac.nowcoder.com/acm/contest/profile-index")
+        .header(REFERER, NOWCODER_PROFILE_INDEX)
         .header(COOKIE, &clean_cookie)
         .send();
 
@@ -120,6 +214,20 @@ pub async fn fetch_user_stats(uid: &str, cookie: &str) -> Result<UserStats> {
     // 并发执行
     let (main_res, practice_res) = tokio::join!(main_req, practice_req);
 
+    // 练习页需要登录，被重定向到登录页即说明 Cookie 已失效
+    // 练习页请求失败时仍返回主页的 Rating (与其他平台一样降级为部分数据)，两者都失败才报错
+    let practice_res = match practice_res {
+        Ok(resp) if is_login_redirect(&resp) => {
+            return Err(SessionExpiredError {
+                site: "NowCoder".to_string(),
+            }
+            .into());
+        }
+        Ok(resp) => Some(resp),
+        Err(e) if main_res.is_err() => return Err(e.into()),
+        Err(_) => None,
+    };
+
     // --- 解析 Rating ---
     let mut rating: Option<u32> = None;
     let mut rank_str: Option<String> = None;
@@ -156,25 +264,28 @@ pub async fn fetch_user_stats(uid: &str, cookie: &str) -> Result<UserStats> {
     // --- 解析 Solved ---
     let mut solved_count = 0;
 
-    if let Ok(resp) = practice_res {
-        if resp.status().is_success() {
-            let html = resp.text().await.unwrap_or_default();
+    if let Some(practice_res) = practice_res.filter(|resp| resp.status().is_success()) {
+        let html = practice_res.text().await.unwrap_or_default();
+        if is_logged_out_page(&html) {
+            return Err(SessionExpiredError {
+                site: "NowCoder".to_string(),
+            }
+            .into());
+        }
 
-            // 针对不同页面结构尝试匹配做题数
-            let re_solved =
-                Regex::new(r#"class="state-num">(\d+)</div>\s*<div[^>]*>\s*<span>题已通过</span>"#)
-                    .unwrap();
-            let re_solved_loose =
-                Regex::new(r#"class="state-num">(\d+)</div>\s*.*题已通过"#).unwrap();
+        // 针对不同页面结构尝试匹配做题数
+        let re_solved =
+            Regex::new(r#"class="state-num">(\d+)</div>\s*<div[^>]*>\s*<span>题已通过</span>"#)
+                .unwrap();
+        let re_solved_loose = Regex::new(r#"class="state-num">(\d+)</div>\s*.*题已通过"#).unwrap();
 
-            if let Some(caps) = re_solved.captures(&html) {
-                if let Ok(num) = caps[1].parse::<u32>() {
-                    solved_count = num;
-                }
-            } else if let Some(caps) = re_solved_loose.captures(&html) {
-                if let Ok(num) = caps[1].parse::<u32>() {
-                    solved_count = num;
-                }
+        if let Some(caps) = re_solved.captures(&html) {
+            if let Ok(num) = caps[1].parse::<u32>() {
+                solved_count = num;
+            }
+        } else if let Some(caps) = re_solved_loose.captures(&html) {
+            if let Ok(num) = caps[1].parse::<u32>() {
+                solved_count = num;
             }
         }
     }
@@ -188,59 +299,197 @@ pub async fn fetch_user_stats(uid: &str, cookie: &str) -> Result<UserStats> {
     })
 }
 
-fn parse_nowcoder_time(text: &str) -> (Option<chrono::DateTime<Utc>>, bool) {
-    let keywords = ["比赛时间", "Start Time", "开始时间"];
-    let mut search_start_index = 0;
-    let mut found_keyword = false;
-
-    for kw in keywords {
-        if let Some(idx) = text.find(kw) {
-            search_start_index = idx;
-            found_keyword = true;
-            break;
-        }
+// [新增] 个人主页 Rating 曲线使用的接口
+const NOWCODER_RATING_HISTORY_URL: &str = "https://ac.nowcoder.com/acm/contest/rating-history";
+
+#[derive(Deserialize)]
+struct RatingHistoryResponse {
+    code: i32,
+    #[serde(default)]
+    msg: String,
+    #[serde(default)]
+    data: Vec<RatingHistoryItem>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RatingHistoryItem {
+    contest_id: i64,
+    contest_name: String,
+    rating: f64,
+    #[serde(default)]
+    rank: Option<u32>,
+    // 本场 Rating 变化量
+    #[serde(default)]
+    change_value: Option<f64>,
+    // 毫秒时间戳
+    time: i64,
+}
+
+// [新增] 统一格式的 Rating 历史；Cookie 为选填
+pub async fn fetch_rating_history(uid: &str, cookie: Option<&str>) -> Result<Vec<RatingEvent>> {
+    let client = Client::builder()
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36")
+        .build()?;
+
+    let mut req = client
+        .get(NOWCODER_RATING_HISTORY_URL)
+        .query(&[("uid", uid)])
+        .header(
+            REFERER,
+            format!("https://ac.nowcoder.com/acm/contest/profile/{}", uid),
+        );
+    if let Some(cookie) = cookie.map(clean_cookie).filter(|c| !c.is_empty()) {
+        req = req.header(COOKIE, cookie);
     }
-    if !found_keyword && text.contains("报名时间") {
-        return (None, false);
+
+    let resp: RatingHistoryResponse = req.send().await?.json().await?;
+    if resp.code != 0 {
+        return Err(anyhow::anyhow!(
+            "NowCoder rating history error: {}",
+            resp.msg
+        ));
     }
-    let relevant_text = &text[search_start_index..];
-    let clean_text = relevant_text.replace('\n', " ").replace('\r', "");
-    let chars: Vec<char> = clean_text.chars().collect();
-    let len = chars.len();
-    if len < 7 {
-        return (None, false);
+
+    let mut events: Vec<RatingEvent> = resp
+        .data
+        .into_iter()
+        .map(|item| RatingEvent {
+            platform: "NowCoder".to_string(),
+            contest_id: item.contest_id.to_string(),
+            contest_name: item.contest_name,
+            time: DateTime::from_timestamp_millis(item.time),
+            old_rating: item
+                .change_value
+                .map(|delta| (item.rating - delta).round() as i32),
+            new_rating: item.rating.round() as i32,
+            rank: item.rank,
+            performance: None,
+        })
+        .collect();
+
+    events.sort_by_key(|e| e.time);
+    Ok(events)
+}
+
+// [新增] 检查 Cookie 是否处于登录态、属于哪个 uid、大致何时过期
+// 访问 profile-index：已登录会 302 到 /acm/contest/profile/{uid}，未登录则跳转登录页
+pub async fn validate_cookie(cookie: &str) -> Result<NowCoderSession> {
+    let clean_cookie = clean_cookie(cookie);
+    if clean_cookie.is_empty() {
+        return Ok(NowCoderSession {
+            logged_in: false,
+            uid: None,
+            expires_at: None,
+        });
     }
-    let china_timezone = FixedOffset::east_opt(8 * 3600).unwrap();
-    let current_year = Local::now().year();
-    for i in 0..len {
-        if !chars[i].is_numeric() {
-            continue;
-        }
-        if i + 16 <= len {
-            let slice: String = chars[i..i + 16].iter().collect();
-            if let Ok(naive) = NaiveDateTime::parse_from_str(&slice, "%Y-%m-%d %H:%M") {
-                let dt = china_timezone.from_local_datetime(&naive).single();
-                return match dt {
-                    Some(t) => (Some(t.with_timezone(&Utc)), true),
-                    None => (None, false),
-                };
-            }
+
+    // 关闭自动重定向，以便读取 Location 与 Set-Cookie
+    let client = Client::builder()
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36")
+        .redirect(reqwest::redirect::Policy::none())
+        .build()?;
+
+    let resp = client
+        .get(NOWCODER_PROFILE_INDEX)
+        .header(COOKIE, &clean_cookie)
+        .send()
+        .await?;
+
+    let expires_at = session_expiry(&resp);
+    let re_uid = Regex::new(r"/acm/contest/profile/(\d+)").unwrap();
+
+    let uid = if resp.status().is_redirection() {
+        resp.headers()
+            .get(LOCATION)
+            .and_then(|h| h.to_str().ok())
+            .and_then(|loc| re_uid.captures(loc))
+            .map(|c| c[1].to_string())
+    } else if resp.status().is_success() {
+        // 部分情况下直接返回页面，从页面脚本中读取当前用户
+        let html = resp.text().await.unwrap_or_default();
+        let re_owner = Regex::new(r#"ownerId\s*:\s*['"]?(\d+)"#).unwrap();
+        if is_logged_out_page(&html) {
+            None
+        } else {
+            re_owner.captures(&html).map(|c| c[1].to_string())
         }
-        if i + 11 <= len {
-            let slice: String = chars[i..i + 11].iter().collect();
-            if slice.chars().nth(2) == Some('-')
-                && slice.chars().nth(5) == Some(' ')
-                && slice.chars().nth(8) == Some(':')
-            {
-                let full_date_str = format!("{}-{}", current_year, slice);
-                if let Ok(naive) = NaiveDateTime::parse_from_str(&full_date_str, "%Y-%m-%d %H:%M") {
-                    if let Some(t) = china_timezone.from_local_datetime(&naive).single() {
-                        return (Some(t.with_timezone(&Utc)), true);
-                    }
-                    return (None, false);
+    } else {
+        None
+    };
+
+    Ok(NowCoderSession {
+        logged_in: uid.is_some(),
+        uid,
+        expires_at,
+    })
+}
+
+/// 清理 Cookie 中的换行符，防止 Header 报错
+fn clean_cookie(cookie: &str) -> String {
+    cookie.replace(['\n', '\r'], "").trim().to_string()
+}
+
+/// 是否被重定向到了登录页 (reqwest 默认跟随重定向，检查最终 URL)
+fn is_login_redirect(resp: &Response) -> bool {
+    resp.url().path().contains("login")
+}
+
+/// 页面脚本中标记了未登录
+fn is_logged_out_page(html: &str) -> bool {
+    let re = Regex::new(r#"isLogin["']?\s*:\s*false"#).unwrap();
+    re.is_match(html)
+}
+
+/// 从 Set-Cookie 中读取登录令牌 `t` 的过期时间
+fn session_expiry(resp: &Response) -> Option<DateTime<Utc>> {
+    resp.headers()
+        .get_all(SET_COOKIE)
+        .iter()
+        .filter_map(|h| h.to_str().ok())
+        .filter(|raw| raw.trim_start().starts_with("t="))
+        .find_map(|raw| {
+            raw.split(';').skip(1).find_map(|attr| {
+                let (key, value) = attr.trim().split_once('=')?;
+                if key.eq_ignore_ascii_case("Max-Age") {
+                    let secs: i64 = value.trim().parse().ok()?;
+                    Some(Utc::now() + Duration::seconds(secs))
+                } else if key.eq_ignore_ascii_case("Expires") {
+                    DateTime::parse_from_rfc2822(value.trim())
+                        .ok()
+                        .map(|t| t.with_timezone(&Utc))
+                } else {
+                    None
                 }
-            }
-        }
+            })
+        })
+}
+
+fn parse_nowcoder_time(text: &str) -> (Option<chrono::DateTime<Utc>>, bool) {
+    match find_contest_time(text) {
+        Some((start, _)) => (Some(start), true),
+        None => (None, false),
     }
-    (None, false)
+}
+
+// [新增] 比赛时间形如 "2024-05-01 19:00 至 2024-05-01 21:00"，取开始时间之后的第二个时间
+fn parse_nowcoder_end_time(text: &str) -> Option<chrono::DateTime<Utc>> {
+    let (_, rest_index) = find_contest_time(text)?;
+    time_parse::find_datetime(&text[rest_index..], time_parse::china(), Utc::now())
+        .map(|(end, _)| end)
+}
+
+// 定位 "比赛时间" 等关键字后的第一个时间 (北京时间)，同时返回其在原文中的结束位置
+fn find_contest_time(text: &str) -> Option<(chrono::DateTime<Utc>, usize)> {
+    let keywords = ["比赛时间", "Start Time", "开始时间"];
+    let search_start_index = match keywords.iter().find_map(|kw| text.find(kw)) {
+        Some(idx) => idx,
+        // 只有报名时间的条目不是比赛时间
+        None if text.contains("报名时间") => return None,
+        None => 0,
+    };
+
+    let (start, end) =
+        time_parse::find_datetime(&text[search_start_index..], time_parse::china(), Utc::now())?;
+    Some((start, search_start_index + end))
 }
@@ -0,0 +1,14 @@
+use crate::models::{Contest, UserStats};
+use crate::platforms::uoj;
+use anyhow::Result;
+
+// QOJ 基于 UOJ 社区版搭建 (Universal Cup 等赛事)，页面结构与 uoj.ac 一致
+const QOJ_DOMAIN: &str = "https://qoj.ac";
+
+pub async fn fetch_contests() -> Result<Vec<Contest>> {
+    uoj::fetch_uoj_like_contests(QOJ_DOMAIN, "QOJ").await
+}
+
+pub async fn fetch_user_stats(handle: &str) -> Result<UserStats> {
+    uoj::fetch_uoj_like_user_stats(QOJ_DOMAIN, "QOJ", handle).await
+}
@@ -0,0 +1,75 @@
+use crate::models::Contest;
+use crate::time_parse;
+use anyhow::Result;
+use chrono::Utc;
+use serde::Deserialize;
+
+// SRM / TCO / Marathon Match 都属于 Data Science 赛道
+const TC_CHALLENGES_URL: &str = "https://api.topcoder.com/v5/challenges";
+const TC_CHALLENGE_PAGE: &str = "https://www.topcoder.com/challenges";
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TcChallenge {
+    id: String,
+    name: String,
+    // e.g. "Single Round Match" / "Marathon Match"
+    #[serde(default)]
+    r#type: Option<String>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+}
+
+// 只关心算法类比赛，其余 Data Science 挑战 (如 First2Finish) 不展示
+fn is_match(challenge: &TcChallenge) -> bool {
+    let name = challenge.name.to_uppercase();
+    let kind = challenge.r#type.as_deref().unwrap_or_default();
+    name.contains("SRM")
+        || name.contains("TCO")
+        || name.contains("SINGLE ROUND MATCH")
+        || kind.contains("Match")
+}
+
+pub async fn fetch_contests() -> Result<Vec<Contest>> {
+    let client = reqwest::Client::new();
+
+    // 已开放报名 (Active) 的比赛包含进行中与即将开始的场次
+    let raw: Vec<TcChallenge> = client
+        .get(TC_CHALLENGES_URL)
+        .query(&[
+            ("tracks[]", "DS"),
+            ("status", "Active"),
+            ("perPage", "100"),
+            ("sortBy", "startDate"),
+            ("sortOrder", "asc"),
+        ])
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    let now = Utc::now();
+    let contests = raw
+        .into_iter()
+        .filter(is_match)
+        .filter_map(|c| {
+            let start_time = c.start_date.as_deref().and_then(time_parse::parse_iso)?;
+            let end_time = c.end_date.as_deref().and_then(time_parse::parse_iso);
+            // 已结束但状态尚未关闭的场次
+            if end_time.is_some_and(|end| end <= now) {
+                return None;
+            }
+            Some(Contest {
+                platform: "TopCoder".to_string(),
+                url: format!("{}/{}", TC_CHALLENGE_PAGE, c.id),
+                name: c.name,
+                start_time,
+                end_time,
+                category: c.r#type,
+                ..Default::default()
+            })
+        })
+        .collect();
+
+    Ok(contests)
+}
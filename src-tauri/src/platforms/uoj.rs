@@ -0,0 +1,123 @@
+use crate::models::{Contest, UserStats};
+use crate::time_parse;
+use anyhow::{anyhow, Result};
+use chrono::{Duration, Utc};
+use regex::Regex;
+use scraper::{Html, Selector};
+
+const UOJ_DOMAIN: &str = "https://uoj.ac";
+
+pub async fn fetch_contests() -> Result<Vec<Contest>> {
+    fetch_uoj_like_contests(UOJ_DOMAIN, "UOJ").await
+}
+
+pub async fn fetch_user_stats(handle: &str) -> Result<UserStats> {
+    fetch_uoj_like_user_stats(UOJ_DOMAIN, "UOJ", handle).await
+}
+
+// UOJ 社区版 (QOJ 等) 与 uoj.ac 共用同一套页面模板，解析逻辑放在这里供其复用
+// 比赛列表: 比赛名称 | 开始时间 | 时长 | 参赛人数 | ...，时间均为北京时间
+pub(crate) async fn fetch_uoj_like_contests(domain: &str, platform: &str) -> Result<Vec<Contest>> {
+    let client = reqwest::Client::builder()
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        .build()?;
+
+    let html = client
+        .get(format!("{}/contests", domain))
+        .send()
+        .await?
+        .text()
+        .await?;
+    let document = Html::parse_document(&html);
+
+    let row_selector = Selector::parse("table tbody tr").unwrap();
+    let link_selector = Selector::parse(r#"a[href*="/contest/"]"#).unwrap();
+    // "5小时" / "5 hours" / "2.5 hours"
+    let re_duration = Regex::new(r"(\d+(?:\.\d+)?)\s*(?:小时|hour)").unwrap();
+
+    let now = Utc::now();
+    let mut contests = Vec::new();
+
+    for row in document.select(&row_selector) {
+        let anchor = match row.select(&link_selector).next() {
+            Some(a) => a,
+            None => continue,
+        };
+        let href = anchor.value().attr("href").unwrap_or_default();
+        let name = anchor.text().collect::<String>().trim().to_string();
+        if name.is_empty() {
+            continue;
+        }
+
+        let text = row.text().collect::<Vec<_>>().join(" ");
+        let (start_time, end_pos) = match time_parse::find_datetime(&text, time_parse::china(), now)
+        {
+            Some(found) => found,
+            None => continue,
+        };
+        let end_time = re_duration
+            .captures(&text[end_pos..])
+            .and_then(|caps| caps[1].parse::<f64>().ok())
+            .map(|hours| start_time + Duration::minutes((hours * 60.0).round() as i64));
+
+        // 已结束的比赛 (没有时长时按开始时间判断)
+        if end_time.unwrap_or(start_time) <= now {
+            continue;
+        }
+
+        let url = if href.starts_with("http") {
+            href.to_string()
+        } else {
+            format!("{}{}", domain, href)
+        };
+
+        contests.push(Contest {
+            platform: platform.to_string(),
+            name,
+            start_time,
+            url,
+            end_time,
+            ..Default::default()
+        });
+    }
+
+    Ok(contests)
+}
+
+// 个人主页: "AC 过的题目：共 N 道题"，Rating 显示在用户名旁
+pub(crate) async fn fetch_uoj_like_user_stats(
+    domain: &str,
+    platform: &str,
+    handle: &str,
+) -> Result<UserStats> {
+    let client = reqwest::Client::builder()
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        .build()?;
+
+    let resp = client
+        .get(format!("{}/user/profile/{}", domain, handle))
+        .send()
+        .await?;
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(anyhow!("{} user '{}' not found", platform, handle));
+    }
+    let html = resp.text().await?;
+
+    let re_solved = Regex::new(r"共\s*(\d+)\s*道题|(\d+)\s*problems? in total").unwrap();
+    let solved_count = re_solved
+        .captures(&html)
+        .and_then(|c| c.get(1).or_else(|| c.get(2)))
+        .and_then(|m| m.as_str().parse().ok())
+        .ok_or_else(|| anyhow!("{} profile parse failed", platform))?;
+
+    let re_rating = Regex::new(r"Rating\s*[:：]\s*(?:<[^>]+>\s*)*(\d+)").unwrap();
+    let rating = re_rating.captures(&html).and_then(|c| c[1].parse().ok());
+
+    Ok(UserStats {
+        platform: platform.to_string(),
+        handle: handle.to_string(),
+        solved_count,
+        rank: None,
+        rating,
+    })
+}
@@ -0,0 +1,78 @@
+use crate::models::{Contest, UserStats};
+use crate::time_parse;
+use anyhow::Result;
+use serde::Deserialize;
+
+const YUKICODER_API_URL: &str = "https://yukicoder.me/api/v1";
+const YUKICODER_DOMAIN: &str = "https://yukicoder.me";
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct YkContest {
+    id: u64,
+    name: String,
+    // e.g. "2024-05-03T21:20:00+09:00"
+    date: String,
+    end_date: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct YkUser {
+    name: String,
+    // 通过题数
+    solved: u32,
+    // 按分数排名；无记录的用户为 0
+    #[serde(default)]
+    rank: u32,
+    // yukicoder 没有 Rating，Level 为按通过题目难度计算的小数
+    #[serde(default)]
+    level: f64,
+}
+
+pub async fn fetch_contests() -> Result<Vec<Contest>> {
+    let client = reqwest::Client::new();
+
+    let url = format!("{}/contest/future", YUKICODER_API_URL);
+    let raw: Vec<YkContest> = client.get(&url).send().await?.json().await?;
+
+    let contests = raw
+        .into_iter()
+        .filter_map(|c| {
+            Some(Contest {
+                platform: "yukicoder".to_string(),
+                start_time: time_parse::parse_iso(&c.date)?,
+                end_time: c.end_date.as_deref().and_then(time_parse::parse_iso),
+                url: format!("{}/contests/{}", YUKICODER_DOMAIN, c.id),
+                name: c.name,
+                ..Default::default()
+            })
+        })
+        .collect();
+
+    Ok(contests)
+}
+
+pub async fn fetch_user_stats(handle: &str) -> Result<UserStats> {
+    let client = reqwest::Client::new();
+
+    let url = format!("{}/user/name/{}", YUKICODER_API_URL, handle);
+    let resp = client.get(&url).send().await?;
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(anyhow::anyhow!("yukicoder user '{}' not found", handle));
+    }
+    let user: YkUser = resp.json().await?;
+
+    let rank = match user.rank {
+        0 => format!("Level {:.1}", user.level),
+        r => format!("Level {:.1} #{}", user.level, r),
+    };
+
+    Ok(UserStats {
+        platform: "yukicoder".to_string(),
+        handle: user.name,
+        solved_count: user.solved,
+        rank: Some(rank),
+        rating: None,
+    })
+}
@@ -0,0 +1,563 @@
+// 文件路径: src-tauri/src/refresh.rs
+//
+// 比赛列表的抓取与后台自动刷新。
+// 比赛来源分为各平台原生爬虫、自定义爬虫 (custom) 与 clist.by 聚合 (clist)，
+// 每个来源单独保存最近一次的结果，后台每分钟检查一次，按设置中各来源的间隔重新抓取到期的来源，
+// 合并后写入缓存，并在列表有变化时发出事件，前端与提醒模块据此更新而无需轮询：
+//   contests-updated: 完整列表 + 新增 / 改期 / 改名 / 取消的比赛及变更记录 (见 ContestsUpdate)
+//   stats-updated:    设置中登记的账号战绩有变化时推送新旧两份数据
+// 单个来源抓取失败时沿用其上一次的结果，暂时的网络错误不会被当成比赛取消。
+
+use crate::cache;
+use crate::changes;
+use crate::models::{
+    Contest, ContestFetchOptions, ContestRename, ContestReschedule, ContestsUpdate,
+    RefreshSettings, StatsUpdate, UserStats,
+};
+use crate::platforms;
+use crate::reminders;
+use crate::tray;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::{AppHandle, Emitter};
+use tokio::task::JoinSet;
+
+const SETTINGS_FILE: &str = "refresh.json";
+const CHECK_INTERVAL_SECS: u64 = 60;
+// 刷新间隔上限 (分钟)，超出的设置按上限处理，避免换算 Duration 时溢出
+const MAX_INTERVAL_MINUTES: u64 = 7 * 24 * 60;
+
+// 原生爬虫来源，名称即 Contest.platform
+// 目前包含: CF, AtCoder, NowCoder, LeetCode, HDU, Luogu, Daimayuan, CodeChef, yukicoder,
+// TopCoder, AcWing, UOJ, QOJ (共13个；LibreOJ 新版没有比赛模块)
+const NATIVE_SOURCES: &[&str] = &[
+    "Codeforces",
+    "AtCoder",
+    "NowCoder",
+    "LeetCode",
+    "HDU",
+    "Luogu",
+    "Daimayuan",
+    "CodeChef",
+    "yukicoder",
+    "TopCoder",
+    "AcWing",
+    "UOJ",
+    "QOJ",
+];
+// 配置目录中的自定义爬虫，平台名由定义文件给出
+const CUSTOM_SOURCE: &str = "custom";
+// clist.by 聚合源，只补充原生爬虫未成功的平台
+const CLIST_SOURCE: &str = "clist";
+
+static STORAGE_DIR: OnceLock<PathBuf> = OnceLock::new();
+static SETTINGS: OnceLock<Mutex<RefreshSettings>> = OnceLock::new();
+// 来源 -> 最近一次抓取的状态
+static SOURCES: Mutex<BTreeMap<&str, SourceState>> = Mutex::new(BTreeMap::new());
+// (平台, handle) -> 最近一次获取的战绩
+static STATS: Mutex<BTreeMap<(String, String), UserStats>> = Mutex::new(BTreeMap::new());
+static STATS_FETCHED_AT: Mutex<Option<DateTime<Utc>>> = Mutex::new(None);
+
+struct SourceState {
+    // 最近一次成功抓取的结果
+    contests: Vec<Contest>,
+    // 最近一次抓取是否成功
+    ok: bool,
+    fetched_at: DateTime<Utc>,
+}
+
+/// 设置文件目录 (应用数据目录)，只在启动时调用一次
+pub fn set_storage_dir(dir: PathBuf) {
+    let _ = STORAGE_DIR.set(dir);
+}
+
+fn settings_path() -> Option<PathBuf> {
+    STORAGE_DIR.get().map(|dir| dir.join(SETTINGS_FILE))
+}
+
+fn settings_store() -> &'static Mutex<RefreshSettings> {
+    SETTINGS.get_or_init(|| {
+        let settings = settings_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .map(clamp_intervals)
+            .unwrap_or_default();
+        Mutex::new(settings)
+    })
+}
+
+// 把各项间隔限制在 MAX_INTERVAL_MINUTES 以内，0 (不自动刷新) 保持不变
+fn clamp_intervals(mut settings: RefreshSettings) -> RefreshSettings {
+    settings.interval_minutes = settings.interval_minutes.min(MAX_INTERVAL_MINUTES);
+    settings.stats_interval_minutes = settings.stats_interval_minutes.min(MAX_INTERVAL_MINUTES);
+    for minutes in settings.platform_intervals.values_mut() {
+        *minutes = (*minutes).min(MAX_INTERVAL_MINUTES);
+    }
+    settings
+}
+
+pub fn settings() -> RefreshSettings {
+    settings_store().lock().unwrap().clone()
+}
+
+pub fn set_settings(settings: RefreshSettings) {
+    let settings = clamp_intervals(settings);
+    *settings_store().lock().unwrap() = settings.clone();
+
+    let Some(path) = settings_path() else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&settings) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+fn all_sources() -> impl Iterator<Item = &'static str> {
+    NATIVE_SOURCES
+        .iter()
+        .copied()
+        .chain([CUSTOM_SOURCE, CLIST_SOURCE])
+}
+
+async fn fetch_source(source: &str, options: &ContestFetchOptions) -> Result<Vec<Contest>> {
+    match source {
+        "Codeforces" => platforms::codeforces::fetch_contests().await,
+        "AtCoder" => platforms::atcoder::fetch_contests().await,
+        "NowCoder" => platforms::nowcoder::fetch_contests(&options.nowcoder).await,
+        "LeetCode" => platforms::leetcode::fetch_contests().await,
+        "HDU" => platforms::hdu::fetch_contests(&options.hdu).await,
+        "Luogu" => platforms::luogu::fetch_contests(&options.luogu).await,
+        "Daimayuan" => platforms::daimayuan::fetch_contests().await,
+        "CodeChef" => platforms::codechef::fetch_contests().await,
+        "yukicoder" => platforms::yukicoder::fetch_contests().await,
+        "TopCoder" => platforms::topcoder::fetch_contests().await,
+        "AcWing" => platforms::acwing::fetch_contests().await,
+        "UOJ" => platforms::uoj::fetch_contests().await,
+        "QOJ" => platforms::qoj::fetch_contests().await,
+        CUSTOM_SOURCE => platforms::custom::fetch_contests().await,
+        // 未配置账号时返回空列表
+        CLIST_SOURCE => platforms::clist::fetch_contests(&options.clist).await,
+        _ => Err(anyhow!("Unknown contest source: {}", source)),
+    }
+}
+
+/// 抓取全部来源 (前端刷新与托盘的 "立即刷新")，返回合并后的完整列表
+pub async fn refresh_all(app: &AppHandle, options: ContestFetchOptions) -> Vec<Contest> {
+    refresh_sources(app, all_sources().collect(), options).await
+}
+
+/// 并发抓取指定来源并合并进缓存；列表有变化时发出 contests-updated
+async fn refresh_sources(
+    app: &AppHandle,
+    sources: Vec<&'static str>,
+    options: ContestFetchOptions,
+) -> Vec<Contest> {
+    let options = Arc::new(options);
+    let mut tasks = JoinSet::new();
+    for source in sources {
+        let options = options.clone();
+        tasks.spawn(async move { (source, fetch_source(source, &options).await) });
+    }
+
+    let mut results = Vec::new();
+    while let Some(res) = tasks.join_next().await {
+        if let Ok(result) = res {
+            results.push(result);
+        }
+    }
+
+    let now = Utc::now();
+    let (contests, update) = apply(results, now);
+    if let Some(mut update) = update {
+        update.changes = changes::record(&update, now);
+        reminders::reschedule(app, &update.changes);
+        let _ = app.emit("contests-updated", &update);
+        tray::refresh_menu(app);
+    }
+    contests
+}
+
+/// 记录各来源的抓取结果，重新合并并写入缓存
+/// 返回合并后的列表，以及与缓存中旧列表不同时的变化
+fn apply(
+    results: Vec<(&'static str, Result<Vec<Contest>>)>,
+    now: DateTime<Utc>,
+) -> (Vec<Contest>, Option<ContestsUpdate>) {
+    // 持有锁直到写入缓存，保证并发的刷新按顺序比较新旧列表
+    let mut sources = SOURCES.lock().unwrap();
+    let old = cache::contests();
+
+    update_sources(&mut sources, results, &old, now);
+    let contests = assemble(&sources, now);
+    cache::store_contests(&contests);
+    if contests == old {
+        return (contests, None);
+    }
+
+    let update = diff(&old, &contests, &covered_platforms(&sources), now);
+    (contests, Some(update))
+}
+
+// 记录各来源本轮的抓取结果
+fn update_sources(
+    sources: &mut BTreeMap<&'static str, SourceState>,
+    results: Vec<(&'static str, Result<Vec<Contest>>)>,
+    old: &[Contest],
+    now: DateTime<Utc>,
+) {
+    for (source, res) in results {
+        match res {
+            Ok(contests) => {
+                sources.insert(
+                    source,
+                    SourceState {
+                        contests,
+                        ok: true,
+                        fetched_at: now,
+                    },
+                );
+            }
+            Err(_) => {
+                // 沿用上一次的结果；启动后首次抓取就失败时，从缓存中取回该平台的比赛
+                let state = sources.entry(source).or_insert_with(|| SourceState {
+                    contests: old
+                        .iter()
+                        .filter(|c| c.platform == source)
+                        .cloned()
+                        .collect(),
+                    ok: false,
+                    fetched_at: now,
+                });
+                state.ok = false;
+                state.fetched_at = now;
+            }
+        }
+    }
+}
+
+/// 合并各来源：原生与自定义爬虫的结果全部保留，clist 只补充原生爬虫未成功的平台
+fn assemble(sources: &BTreeMap<&str, SourceState>, now: DateTime<Utc>) -> Vec<Contest> {
+    // 失败来源沿用的旧数据中去掉已结束的比赛
+    let live = |state: &SourceState| -> Vec<Contest> {
+        state
+            .contests
+            .iter()
+            .filter(|c| state.ok || c.end_time.unwrap_or(c.start_time) > now)
+            .cloned()
+            .collect()
+    };
+
+    let mut contests = Vec::new();
+    let mut succeeded: HashSet<&str> = HashSet::new();
+    for (&source, state) in sources {
+        if source == CLIST_SOURCE {
+            continue;
+        }
+        if state.ok && NATIVE_SOURCES.contains(&source) {
+            succeeded.insert(source);
+        }
+        contests.extend(live(state));
+    }
+    if let Some(state) = sources.get(CLIST_SOURCE) {
+        platforms::clist::merge_fallback(&mut contests, &succeeded, live(state));
+    }
+
+    // 统一按开始时间排序
+    contests.sort_by_key(|c| c.start_time);
+    contests
+}
+
+// 本轮数据可信的平台：抓取成功的原生平台，以及成功的自定义爬虫 / clist 实际返回了比赛的平台
+fn covered_platforms(sources: &BTreeMap<&str, SourceState>) -> HashSet<String> {
+    let mut covered = HashSet::new();
+    for (&source, state) in sources.iter().filter(|(_, s)| s.ok) {
+        if NATIVE_SOURCES.contains(&source) {
+            covered.insert(source.to_string());
+        } else {
+            covered.extend(state.contests.iter().map(|c| c.platform.clone()));
+        }
+    }
+    covered
+}
+
+/// 以比赛 id 为键比较新旧列表
+/// 已开始的比赛从列表中消失属于正常结束；只有原定尚未开始、且所属平台本轮数据可信时才算取消
+fn diff(
+    old: &[Contest],
+    contests: &[Contest],
+    covered: &HashSet<String>,
+    now: DateTime<Utc>,
+) -> ContestsUpdate {
+    let old_by_id: HashMap<&str, &Contest> = old.iter().map(|c| (c.id(), c)).collect();
+    let new_ids: HashSet<&str> = contests.iter().map(|c| c.id()).collect();
+
+    let mut update = ContestsUpdate {
+        contests: contests.to_vec(),
+        ..Default::default()
+    };
+    for contest in contests {
+        let Some(prev) = old_by_id.get(contest.id()) else {
+            update.added.push(contest.clone());
+            continue;
+        };
+        if prev.start_time != contest.start_time || prev.end_time != contest.end_time {
+            update.rescheduled.push(ContestReschedule {
+                contest: contest.clone(),
+                old_start_time: prev.start_time,
+                old_end_time: prev.end_time,
+            });
+        }
+        if prev.name != contest.name {
+            update.renamed.push(ContestRename {
+                contest: contest.clone(),
+                old_name: prev.name.clone(),
+            });
+        }
+    }
+    update.cancelled = old
+        .iter()
+        .filter(|c| !new_ids.contains(c.id()))
+        .filter(|c| c.start_time > now && covered.contains(&c.platform))
+        .cloned()
+        .collect();
+    update
+}
+
+/// 启动后台刷新循环
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            // 先等待一个周期：启动时前端会自行拉取一次完整列表
+            tokio::time::sleep(std::time::Duration::from_secs(CHECK_INTERVAL_SECS)).await;
+
+            let settings = settings();
+            if !settings.enabled {
+                continue;
+            }
+            let now = Utc::now();
+
+            let due = due_sources(&settings, now);
+            if !due.is_empty() {
+                refresh_sources(&app, due, cache::fetch_options()).await;
+            }
+            refresh_stats(&app, &settings, now).await;
+        }
+    });
+}
+
+// 来源的刷新间隔 (分钟)，平台名不区分大小写
+fn interval_for(settings: &RefreshSettings, source: &str) -> u64 {
+    settings
+        .platform_intervals
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(source))
+        .map(|(_, minutes)| *minutes)
+        .unwrap_or(settings.interval_minutes)
+}
+
+fn due_sources(settings: &RefreshSettings, now: DateTime<Utc>) -> Vec<&'static str> {
+    let sources = SOURCES.lock().unwrap();
+    all_sources()
+        .filter(|source| {
+            let minutes = interval_for(settings, source);
+            if minutes == 0 {
+                return false;
+            }
+            match sources.get(source) {
+                Some(state) => now - state.fetched_at >= Duration::minutes(minutes as i64),
+                None => true,
+            }
+        })
+        .collect()
+}
+
+/// 按间隔刷新登记账号的战绩，有变化时发出 stats-updated
+async fn refresh_stats(app: &AppHandle, settings: &RefreshSettings, now: DateTime<Utc>) {
+    if settings.stats_interval_minutes == 0 || settings.accounts.is_empty() {
+        return;
+    }
+    {
+        let mut fetched_at = STATS_FETCHED_AT.lock().unwrap();
+        if let Some(last) = *fetched_at {
+            if now - last < Duration::minutes(settings.stats_interval_minutes as i64) {
+                return;
+            }
+        }
+        *fetched_at = Some(now);
+    }
+
+    for account in &settings.accounts {
+        let Ok(stats) = crate::fetch_user_stats(
+            account.platform.clone(),
+            account.handle.clone(),
+            account.cookie.clone(),
+            account.region.clone(),
+        )
+        .await
+        else {
+            continue;
+        };
+
+        let key = (account.platform.to_lowercase(), account.handle.clone());
+        let previous = STATS.lock().unwrap().insert(key, stats.clone());
+        if previous.as_ref() != Some(&stats) {
+            let _ = app.emit(
+                "stats-updated",
+                StatsUpdate {
+                    platform: account.platform.clone(),
+                    handle: account.handle.clone(),
+                    stats,
+                    previous,
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_support::{contest, now};
+
+    fn state(contests: Vec<Contest>, ok: bool) -> SourceState {
+        SourceState {
+            contests,
+            ok,
+            fetched_at: now(),
+        }
+    }
+
+    fn covered(platforms: &[&str]) -> HashSet<String> {
+        platforms.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn diff_reports_added_contests() {
+        let old = vec![contest("Codeforces", "1", "Round 1", 5)];
+        let new = vec![
+            contest("Codeforces", "1", "Round 1", 5),
+            contest("Codeforces", "2", "Round 2", 30),
+        ];
+        let update = diff(&old, &new, &covered(&["Codeforces"]), now());
+        assert_eq!(update.added, vec![new[1].clone()]);
+        assert!(update.rescheduled.is_empty());
+        assert!(update.renamed.is_empty());
+        assert!(update.cancelled.is_empty());
+    }
+
+    #[test]
+    fn diff_reports_start_and_end_changes() {
+        let old = vec![
+            contest("AtCoder", "abc", "ABC 350", 5),
+            contest("AtCoder", "arc", "ARC 176", 30),
+        ];
+        let mut moved = contest("AtCoder", "abc", "ABC 350", 7);
+        moved.end_time = old[0].end_time;
+        let mut longer = old[1].clone();
+        longer.end_time = longer.end_time.map(|t| t + Duration::hours(1));
+        let new = vec![moved.clone(), longer.clone()];
+
+        let update = diff(&old, &new, &covered(&["AtCoder"]), now());
+        assert_eq!(update.rescheduled.len(), 2);
+        assert_eq!(update.rescheduled[0].contest, moved);
+        assert_eq!(update.rescheduled[0].old_start_time, old[0].start_time);
+        assert_eq!(update.rescheduled[1].contest, longer);
+        assert_eq!(update.rescheduled[1].old_start_time, longer.start_time);
+        assert_eq!(update.rescheduled[1].old_end_time, old[1].end_time);
+        assert!(update.added.is_empty());
+        assert!(update.cancelled.is_empty());
+    }
+
+    #[test]
+    fn diff_reports_renamed_contests() {
+        let old = vec![contest("Luogu", "1", "月赛", 5)];
+        let new = vec![contest("Luogu", "1", "【LGR-200】月赛", 5)];
+        let update = diff(&old, &new, &covered(&["Luogu"]), now());
+        assert_eq!(update.renamed.len(), 1);
+        assert_eq!(update.renamed[0].old_name, "月赛");
+        assert_eq!(update.renamed[0].contest.name, "【LGR-200】月赛");
+        assert!(update.rescheduled.is_empty());
+    }
+
+    #[test]
+    fn diff_only_cancels_upcoming_contests_of_covered_platforms() {
+        let upcoming = contest("Codeforces", "1", "Round 1", 5);
+        let started = contest("Codeforces", "2", "Round 2", -1);
+        let uncovered = contest("HDU", "3", "HDU Monthly", 5);
+        let old = vec![started, upcoming.clone(), uncovered];
+
+        let update = diff(&old, &[], &covered(&["Codeforces"]), now());
+        assert_eq!(update.cancelled, vec![upcoming]);
+    }
+
+    #[test]
+    fn failed_source_keeps_previous_contests() {
+        let kept = contest("Codeforces", "1", "Round 1", 5);
+        let ended = contest("Codeforces", "2", "Round 2", -3);
+        let mut sources = BTreeMap::new();
+        sources.insert("Codeforces", state(vec![ended, kept.clone()], true));
+
+        let results = vec![("Codeforces", Err(anyhow!("timeout")))];
+        update_sources(&mut sources, results, &[], now());
+
+        assert!(!sources["Codeforces"].ok);
+        // 失败来源只保留尚未结束的比赛，且不参与取消判断
+        assert_eq!(assemble(&sources, now()), vec![kept]);
+        assert!(covered_platforms(&sources).is_empty());
+    }
+
+    #[test]
+    fn failed_first_fetch_restores_cached_contests() {
+        let cached = vec![
+            contest("AtCoder", "abc", "ABC 350", 5),
+            contest("Codeforces", "1", "Round 1", 6),
+        ];
+        let mut sources = BTreeMap::new();
+        let results = vec![("AtCoder", Err(anyhow!("timeout")))];
+        update_sources(&mut sources, results, &cached, now());
+
+        assert_eq!(sources["AtCoder"].contests, vec![cached[0].clone()]);
+        assert!(!sources["AtCoder"].ok);
+    }
+
+    #[test]
+    fn successful_source_replaces_previous_contests() {
+        let mut sources = BTreeMap::new();
+        sources.insert(
+            "Codeforces",
+            state(vec![contest("Codeforces", "1", "Round 1", 5)], false),
+        );
+        let fresh = vec![contest("Codeforces", "2", "Round 2", 8)];
+        let results = vec![("Codeforces", Ok(fresh.clone()))];
+        update_sources(&mut sources, results, &[], now());
+
+        assert!(sources["Codeforces"].ok);
+        assert_eq!(assemble(&sources, now()), fresh);
+    }
+
+    #[test]
+    fn covered_platforms_follow_successful_sources() {
+        let mut sources = BTreeMap::new();
+        sources.insert("Codeforces", state(Vec::new(), true));
+        sources.insert("AtCoder", state(Vec::new(), false));
+        sources.insert(
+            CUSTOM_SOURCE,
+            state(vec![contest("MyOJ", "x", "Weekly", 5)], true),
+        );
+        sources.insert(
+            CLIST_SOURCE,
+            state(vec![contest("HDU", "y", "HDU Monthly", 5)], false),
+        );
+
+        assert_eq!(
+            covered_platforms(&sources),
+            covered(&["Codeforces", "MyOJ"])
+        );
+    }
+}
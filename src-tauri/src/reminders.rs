@@ -0,0 +1,314 @@
+// 文件路径: src-tauri/src/reminders.rs
+//
+// 比赛提醒调度：后台每 30 秒检查一次缓存中的比赛，对订阅的比赛 (单场或整个平台)
+// 在设定的提前量 (如 1 天 / 1 小时 / 10 分钟) 到达时发送桌面通知。
+// 已发送记录以 (比赛链接, 开始时间, 提前量) 为键持久化，刷新列表或重启后不会重复提醒；
+// 比赛改期后开始时间变化，键随之变化，会按新时间重新提醒。
+// 提醒与每日赛程同时推送到配置的 Webhook (见 webhooks.rs)。
+// 刷新发现比赛改期或取消时 (见 changes.rs)，清除旧开始时间的记录，订阅的比赛另行发送一条通知。
+
+use crate::cache;
+use crate::changes;
+use crate::models::{Contest, ContestChange, ContestChangeKind, ReminderSettings};
+use crate::webhooks;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+const STATE_FILE: &str = "reminders.json";
+const CHECK_INTERVAL_SECS: u64 = 30;
+// 提前量上限 (分钟)
+const MAX_LEAD_MINUTES: i64 = 7 * 24 * 60;
+
+static STORAGE_DIR: OnceLock<PathBuf> = OnceLock::new();
+static STATE: OnceLock<Mutex<ReminderState>> = OnceLock::new();
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
+struct ReminderState {
+    settings: ReminderSettings,
+    // 已发送提醒的键 -> 比赛开始时间 (用于清理过期记录)
+    sent: BTreeMap<String, DateTime<Utc>>,
+    // 最近一次推送每日赛程的本地日期
+    last_digest: Option<NaiveDate>,
+}
+
+/// 设置状态文件目录 (应用数据目录)，只在启动时调用一次
+pub fn set_storage_dir(dir: PathBuf) {
+    let _ = STORAGE_DIR.set(dir);
+}
+
+fn state_path() -> Option<PathBuf> {
+    STORAGE_DIR.get().map(|dir| dir.join(STATE_FILE))
+}
+
+fn state() -> &'static Mutex<ReminderState> {
+    STATE.get_or_init(|| {
+        let state = state_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str::<ReminderState>(&json).ok())
+            .map(|mut state| {
+                // 状态文件可能被手动修改，丢弃超出范围的提前量
+                state
+                    .settings
+                    .lead_minutes
+                    .retain(|&lead| is_valid_lead(lead));
+                state
+            })
+            .unwrap_or_default();
+        Mutex::new(state)
+    })
+}
+
+fn is_valid_lead(lead: i64) -> bool {
+    lead > 0 && lead <= MAX_LEAD_MINUTES
+}
+
+fn save(state: &ReminderState) {
+    let Some(path) = state_path() else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(state) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+pub fn settings() -> ReminderSettings {
+    state().lock().unwrap().settings.clone()
+}
+
+/// 保存提醒设置，提前量须在 1 分钟到 MAX_LEAD_MINUTES 之间
+pub fn set_settings(settings: ReminderSettings) -> Result<()> {
+    if let Some(lead) = settings
+        .lead_minutes
+        .iter()
+        .find(|&&lead| !is_valid_lead(lead))
+    {
+        return Err(anyhow!(
+            "Reminder lead time must be between 1 and {} minutes, got {}",
+            MAX_LEAD_MINUTES,
+            lead
+        ));
+    }
+
+    let mut state = state().lock().unwrap();
+    state.settings = settings;
+    save(&state);
+    Ok(())
+}
+
+/// 启动后台调度循环
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let now = Utc::now();
+            let contests = cache::contests();
+            let hooks = settings().webhooks;
+
+            for (contest, remaining) in due_reminders(&contests, now) {
+                notify(&app, &contest, remaining);
+                webhooks::broadcast_reminder(&hooks, &contest, remaining).await;
+            }
+            if let Some(upcoming) = due_digest(&contests, now) {
+                webhooks::broadcast_digest(&hooks, &upcoming).await;
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(CHECK_INTERVAL_SECS)).await;
+        }
+    });
+}
+
+/// 找出此刻需要提醒的比赛并记为已发送，返回 (比赛, 距开始的剩余时间)
+/// 同一场比赛有多个提前量同时到期 (如刚订阅一场 30 分钟后开始的比赛) 时只提醒一次
+fn due_reminders(contests: &[Contest], now: DateTime<Utc>) -> Vec<(Contest, Duration)> {
+    let mut state = state().lock().unwrap();
+    if !state.settings.enabled {
+        return Vec::new();
+    }
+
+    let mut due = Vec::new();
+    let mut changed = false;
+
+    for contest in contests {
+        let remaining = contest.start_time - now;
+        if remaining <= Duration::zero()
+            || !is_subscribed(&state.settings, contest.id(), &contest.platform)
+        {
+            continue;
+        }
+
+        let keys: Vec<String> = state
+            .settings
+            .lead_minutes
+            .iter()
+            .filter(|&&lead| remaining <= Duration::minutes(lead))
+            .map(|lead| sent_key(contest, *lead))
+            .filter(|key| !state.sent.contains_key(key))
+            .collect();
+        if keys.is_empty() {
+            continue;
+        }
+
+        for key in keys {
+            state.sent.insert(key, contest.start_time);
+        }
+        changed = true;
+        due.push((contest.clone(), remaining));
+    }
+
+    // 清理一天前就已开始的比赛的记录
+    let before = state.sent.len();
+    state
+        .sent
+        .retain(|_, start| *start > now - Duration::days(1));
+    changed |= state.sent.len() != before;
+
+    if changed {
+        save(&state);
+    }
+    due
+}
+
+/// 到了每日赛程推送时间且今天尚未推送时，返回未来 24 小时内开始的比赛
+/// 赛程面向整个团队，不受订阅过滤
+fn due_digest(contests: &[Contest], now: DateTime<Utc>) -> Option<Vec<Contest>> {
+    let mut state = state().lock().unwrap();
+    let at = state
+        .settings
+        .daily_digest
+        .as_deref()
+        .and_then(|t| NaiveTime::parse_from_str(t.trim(), "%H:%M").ok())?;
+
+    let local = now.with_timezone(&Local);
+    let today = local.date_naive();
+    if local.time() < at || state.last_digest == Some(today) || state.settings.webhooks.is_empty() {
+        return None;
+    }
+
+    state.last_digest = Some(today);
+    save(&state);
+
+    Some(
+        contests
+            .iter()
+            .filter(|c| c.start_time > now && c.start_time <= now + Duration::days(1))
+            .cloned()
+            .collect(),
+    )
+}
+
+// [修改] 以比赛 id 与平台判断，变更记录中没有完整的 Contest
+fn is_subscribed(settings: &ReminderSettings, contest_id: &str, platform: &str) -> bool {
+    settings.contests.iter().any(|id| id == contest_id)
+        || settings
+            .platforms
+            .iter()
+            .any(|p| p.eq_ignore_ascii_case(platform))
+}
+
+fn sent_key(contest: &Contest, lead_minutes: i64) -> String {
+    format!(
+        "{}|{}|{}",
+        contest.url,
+        contest.start_time.timestamp(),
+        lead_minutes
+    )
+}
+
+fn notify(app: &AppHandle, contest: &Contest, remaining: Duration) {
+    show(
+        app,
+        format!("{} 比赛即将开始", contest.platform),
+        format!(
+            "{}\n{} 后开始 ({})",
+            contest.name,
+            format_remaining(remaining),
+            local_time(contest.start_time)
+        ),
+    );
+}
+
+/// 按比赛变更重新安排提醒 (由 refresh.rs 在列表更新后调用)
+/// 改期或取消的比赛清除旧开始时间的发送记录；改期后的提醒键随新开始时间变化，按新时间重新提醒。
+/// 订阅的比赛另行通知一次，改名只做记录
+pub fn reschedule(app: &AppHandle, events: &[ContestChange]) {
+    let settings = {
+        let mut state = state().lock().unwrap();
+        let before = state.sent.len();
+        for change in events {
+            let old_start = match change.kind {
+                ContestChangeKind::Rescheduled => change.old_start_time,
+                ContestChangeKind::Cancelled => Some(change.start_time),
+                ContestChangeKind::Renamed => None,
+            };
+            if let Some(old_start) = old_start {
+                let prefix = format!("{}|{}|", change.contest_id, old_start.timestamp());
+                state.sent.retain(|key, _| !key.starts_with(&prefix));
+            }
+        }
+        if state.sent.len() != before {
+            save(&state);
+        }
+        state.settings.clone()
+    };
+    if !settings.enabled {
+        return;
+    }
+
+    for change in events {
+        if !is_subscribed(&settings, &change.contest_id, &change.platform) {
+            continue;
+        }
+        match change.kind {
+            ContestChangeKind::Rescheduled => show(
+                app,
+                format!("{} 比赛时间变更", change.platform),
+                format!(
+                    "{}\n{}: {} → {}",
+                    change.name,
+                    changes::format_shift(change.shift_minutes.unwrap_or_default()),
+                    change.old_start_time.map(local_time).unwrap_or_default(),
+                    local_time(change.start_time)
+                ),
+            ),
+            ContestChangeKind::Cancelled => show(
+                app,
+                format!("{} 比赛已取消", change.platform),
+                format!(
+                    "{}\n原定 {} 开始",
+                    change.name,
+                    local_time(change.start_time)
+                ),
+            ),
+            ContestChangeKind::Renamed => {}
+        }
+    }
+}
+
+fn show(app: &AppHandle, title: String, body: String) {
+    let _ = app.notification().builder().title(title).body(body).show();
+}
+
+fn local_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local).format("%m-%d %H:%M").to_string()
+}
+
+/// "1 天 2 小时" / "45 分钟"
+pub fn format_remaining(remaining: Duration) -> String {
+    let minutes = (remaining.num_seconds() + 59) / 60;
+    let (days, hours, mins) = (minutes / 1440, minutes % 1440 / 60, minutes % 60);
+    match (days, hours) {
+        (0, 0) => format!("{} 分钟", mins),
+        (0, _) if mins == 0 => format!("{} 小时", hours),
+        (0, _) => format!("{} 小时 {} 分钟", hours, mins),
+        (_, 0) => format!("{} 天", days),
+        _ => format!("{} 天 {} 小时", days, hours),
+    }
+}
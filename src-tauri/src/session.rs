@@ -0,0 +1,381 @@
+// 文件路径: src-tauri/src/session.rs
+//
+// 反爬会话组件：为需要过 WAF 的站点 (目前是洛谷) 维护一个 Cookie Jar，
+// 自动跟随多步质询 (302 下发 Cookie / JS 写 Cookie 后刷新)，并把质询 Cookie
+// 持久化到应用数据目录，避免每次启动都重新过一遍质询。
+// 同时定义与会话相关的类型化错误，供各平台模块返回、lib.rs 识别。
+
+use anyhow::Result;
+use regex::Regex;
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use reqwest::{Client, StatusCode, Url};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+// 单次请求最多跟随的质询/重定向步数
+const MAX_HOPS: usize = 5;
+
+const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+
+// Cookie 持久化目录，由 lib.rs 在 setup 阶段设置
+static STORAGE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// 设置 Cookie 持久化目录 (应用数据目录)，只在启动时调用一次
+pub fn set_storage_dir(dir: PathBuf) {
+    let _ = STORAGE_DIR.set(dir);
+}
+
+/// 跟随质询后返回的仍是质询页面
+#[derive(Debug)]
+pub struct BlockedError {
+    pub site: String,
+    pub url: String,
+}
+
+impl fmt::Display for BlockedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Blocked by {} anti-bot challenge: {}",
+            self.site, self.url
+        )
+    }
+}
+
+impl std::error::Error for BlockedError {}
+
+/// 用户提供的登录 Cookie 已失效 (被重定向到登录页)
+#[derive(Debug)]
+pub struct SessionExpiredError {
+    pub site: String,
+}
+
+impl fmt::Display for SessionExpiredError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} session expired, please update the cookie in settings",
+            self.site
+        )
+    }
+}
+
+impl std::error::Error for SessionExpiredError {}
+
+pub struct AntiBotSession {
+    name: &'static str,
+    client: Client,
+    // name -> value，每个会话只服务一个站点，无需按域名区分
+    jar: Mutex<BTreeMap<String, String>>,
+    // 质询页面特征串，命中任意一个即视为仍在质询中
+    challenge_markers: &'static [&'static str],
+}
+
+impl AntiBotSession {
+    pub fn new(name: &'static str, challenge_markers: &'static [&'static str]) -> Result<Self> {
+        // 关闭自动重定向，由会话自己处理以便截获每一步的 Set-Cookie
+        // 不能退回默认 Client：那样会自动跟随重定向，质询 Cookie 就拿不到了
+        let client = Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .user_agent(USER_AGENT)
+            .build()?;
+
+        Ok(Self {
+            name,
+            client,
+            jar: Mutex::new(load_jar(name)),
+            challenge_markers,
+        })
+    }
+
+    /// GET 请求并返回正文
+    /// `extra_cookie` 为用户自己的登录 Cookie，只随请求发送，不写入 Jar
+    pub async fn get_text(
+        &self,
+        url: &str,
+        headers: HeaderMap,
+        extra_cookie: Option<&str>,
+    ) -> Result<String> {
+        let mut current = Url::parse(url)?;
+
+        for _ in 0..MAX_HOPS {
+            let mut req = self.client.get(current.clone()).headers(headers.clone());
+            if let Some(cookie) = self.cookie_header(extra_cookie) {
+                req = req.header(header::COOKIE, cookie);
+            }
+
+            let resp = req.send().await?;
+            let status = resp.status();
+            let mut jar_changed = self.store_set_cookies(resp.headers(), extra_cookie);
+
+            // 1. 重定向式质询：Set-Cookie + Location
+            if status.is_redirection() {
+                let location = resp
+                    .headers()
+                    .get(header::LOCATION)
+                    .and_then(|h| h.to_str().ok())
+                    .and_then(|loc| current.join(loc).ok());
+                if jar_changed {
+                    self.save();
+                }
+                match location {
+                    Some(next) => {
+                        current = next;
+                        continue;
+                    }
+                    None => break,
+                }
+            }
+
+            let text = resp.text().await?;
+
+            // 2. 脚本式质询：页面内通过 document.cookie 写入 Cookie 后刷新
+            if self.is_challenge(status, &text) {
+                jar_changed |= self.store_script_cookies(&text, extra_cookie);
+                if jar_changed {
+                    self.save();
+                    continue;
+                }
+                // 没拿到新 Cookie，再请求也只会得到同样的页面
+                break;
+            }
+
+            if jar_changed {
+                self.save();
+            }
+            return Ok(text);
+        }
+
+        Err(BlockedError {
+            site: self.name.to_string(),
+            url: url.to_string(),
+        }
+        .into())
+    }
+
+    fn is_challenge(&self, status: StatusCode, text: &str) -> bool {
+        let trimmed = text.trim_start();
+        if !trimmed.starts_with('<') {
+            return false;
+        }
+        if self.challenge_markers.iter().any(|m| text.contains(m)) {
+            return true;
+        }
+        // 兜底：403/503 且页面内只有写 Cookie 的脚本
+        matches!(
+            status,
+            StatusCode::FORBIDDEN | StatusCode::SERVICE_UNAVAILABLE
+        ) && text.contains("document.cookie")
+    }
+
+    /// 合并 Jar 与用户 Cookie；同名时以用户的为准，不重复发送 Jar 中的匿名值
+    fn cookie_header(&self, extra_cookie: Option<&str>) -> Option<String> {
+        let extra = extra_cookie.map(str::trim).filter(|c| !c.is_empty());
+        let overridden = extra.map(cookie_names).unwrap_or_default();
+
+        let jar = self.jar.lock().unwrap();
+        let mut parts: Vec<String> = jar
+            .iter()
+            .filter(|(k, _)| !overridden.contains(k.as_str()))
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect();
+        if let Some(c) = extra {
+            parts.push(c.to_string());
+        }
+        (!parts.is_empty()).then(|| parts.join("; "))
+    }
+
+    /// 记录 Set-Cookie，返回 Jar 是否发生变化
+    fn store_set_cookies(&self, headers: &HeaderMap, extra_cookie: Option<&str>) -> bool {
+        let pairs: Vec<(String, String, bool)> = headers
+            .get_all(header::SET_COOKIE)
+            .iter()
+            .filter_map(|h: &HeaderValue| h.to_str().ok())
+            .filter_map(parse_set_cookie)
+            .collect();
+        self.apply(pairs, extra_cookie)
+    }
+
+    fn store_script_cookies(&self, text: &str, extra_cookie: Option<&str>) -> bool {
+        let pairs: Vec<(String, String, bool)> = script_cookie_regex()
+            .captures_iter(text)
+            .filter_map(|caps| parse_set_cookie(&caps[1]))
+            .collect();
+        self.apply(pairs, extra_cookie)
+    }
+
+    /// 写入 Jar；用户 Cookie 中已有的名称属于登录态，不写入共享的 Jar，
+    /// 以免随之后的匿名请求发出或被持久化
+    fn apply(&self, pairs: Vec<(String, String, bool)>, extra_cookie: Option<&str>) -> bool {
+        let user_names = extra_cookie.map(cookie_names).unwrap_or_default();
+        let mut jar = self.jar.lock().unwrap();
+        let mut changed = false;
+        for (name, value, expired) in pairs {
+            if user_names.contains(name.as_str()) {
+                continue;
+            }
+            if expired {
+                changed |= jar.remove(&name).is_some();
+            } else if jar.get(&name) != Some(&value) {
+                jar.insert(name, value);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    fn save(&self) {
+        let Some(path) = jar_path(self.name) else {
+            return;
+        };
+        let jar = self.jar.lock().unwrap().clone();
+        // 持久化失败不影响本次请求
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_string_pretty(&jar) {
+            let _ = std::fs::write(path, json);
+        }
+    }
+}
+
+// 质询页面中写 Cookie 的脚本: document.cookie = "name=value; ..."
+fn script_cookie_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"document\.cookie\s*=\s*["']([^"']+)["']"#).unwrap())
+}
+
+/// 解析 "name=value; Path=/; Max-Age=0"，返回 (name, value, 是否为删除)
+fn parse_set_cookie(raw: &str) -> Option<(String, String, bool)> {
+    let mut segments = raw.split(';');
+    let (name, value) = segments.next()?.trim().split_once('=')?;
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    let expired = value.trim().is_empty()
+        || segments.any(|attr| {
+            attr.trim()
+                .to_ascii_lowercase()
+                .replace(' ', "")
+                .starts_with("max-age=0")
+        });
+
+    Some((name.to_string(), value.trim().to_string(), expired))
+}
+
+/// Cookie 串 "a=1; b=2" 中的名称
+fn cookie_names(cookie: &str) -> HashSet<&str> {
+    cookie
+        .split(';')
+        .filter_map(|part| part.split_once('='))
+        .map(|(name, _)| name.trim())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+fn jar_path(name: &str) -> Option<PathBuf> {
+    STORAGE_DIR
+        .get()
+        .map(|dir| dir.join("cookies").join(format!("{}.json", name)))
+}
+
+fn load_jar(name: &str) -> BTreeMap<String, String> {
+    jar_path(name)
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session_with_jar(pairs: &[(&str, &str)]) -> AntiBotSession {
+        let session = AntiBotSession::new("test", &["C3VK="]).unwrap();
+        session.apply(
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string(), false))
+                .collect(),
+            None,
+        );
+        session
+    }
+
+    #[test]
+    fn user_cookie_overrides_jar_entries() {
+        let session = session_with_jar(&[("C3VK", "abc"), ("__client_id", "anon")]);
+        assert_eq!(
+            session.cookie_header(Some(" __client_id=mine; _uid=42 ")),
+            Some("C3VK=abc; __client_id=mine; _uid=42".to_string())
+        );
+        assert_eq!(
+            session.cookie_header(None),
+            Some("C3VK=abc; __client_id=anon".to_string())
+        );
+    }
+
+    #[test]
+    fn user_cookie_names_are_not_stored() {
+        let session = session_with_jar(&[("C3VK", "abc")]);
+        let mut headers = HeaderMap::new();
+        headers.append(
+            header::SET_COOKIE,
+            HeaderValue::from_static("__client_id=fresh; Path=/; HttpOnly"),
+        );
+        headers.append(
+            header::SET_COOKIE,
+            HeaderValue::from_static("C3VK=def; Path=/"),
+        );
+
+        assert!(session.store_set_cookies(&headers, Some("__client_id=mine; _uid=42")));
+        assert_eq!(session.cookie_header(None), Some("C3VK=def".to_string()));
+    }
+
+    #[test]
+    fn parses_set_cookie_attributes() {
+        assert_eq!(
+            parse_set_cookie("C3VK=abc; Path=/; Max-Age=3600; HttpOnly"),
+            Some(("C3VK".to_string(), "abc".to_string(), false))
+        );
+        assert_eq!(
+            parse_set_cookie(" _uid = 42 ; path=/"),
+            Some(("_uid".to_string(), "42".to_string(), false))
+        );
+        assert_eq!(
+            parse_set_cookie("C3VK=abc; Max-Age = 0"),
+            Some(("C3VK".to_string(), "abc".to_string(), true))
+        );
+        assert_eq!(
+            parse_set_cookie("C3VK=; Path=/"),
+            Some(("C3VK".to_string(), String::new(), true))
+        );
+        assert_eq!(parse_set_cookie("=abc"), None);
+        assert_eq!(parse_set_cookie("no-equals-sign"), None);
+    }
+
+    #[test]
+    fn detects_challenge_pages() {
+        let session = session_with_jar(&[]);
+        let marker = "<html><script>document.cookie=\"C3VK=abc\";location.reload()</script></html>";
+        let script_only = "<html><script>document.cookie=\"x=1\"</script></html>";
+
+        assert!(session.is_challenge(StatusCode::OK, marker));
+        assert!(session.is_challenge(StatusCode::FORBIDDEN, script_only));
+        assert!(session.is_challenge(StatusCode::SERVICE_UNAVAILABLE, script_only));
+        assert!(!session.is_challenge(StatusCode::OK, script_only));
+        assert!(!session.is_challenge(StatusCode::OK, "<html><body>ok</body></html>"));
+        // JSON 接口的正文不会是质询页
+        assert!(!session.is_challenge(StatusCode::FORBIDDEN, r#"{"msg":"document.cookie C3VK="}"#));
+    }
+
+    #[test]
+    fn empty_cookie_header_is_omitted() {
+        let session = session_with_jar(&[]);
+        assert_eq!(session.cookie_header(Some("  ")), None);
+    }
+}
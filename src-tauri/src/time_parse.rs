@@ -0,0 +1,294 @@
+// 文件路径: src-tauri/src/time_parse.rs
+//
+// 各爬虫共用的时间解析。页面上的时间都是站点本地时间，调用方必须显式给出来源时区；
+// 缺少年份的 "MM-DD HH:MM" 取离 now 最近的年份 (12 月底看到的 "01-03" 属于明年)。
+// now 由调用方传入，方便在不同时间点下复现解析结果。
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use regex::{Captures, Regex};
+use std::sync::OnceLock;
+
+/// 北京时间 (NowCoder / HDU / 洛谷 / 代码源等国内站点)
+pub fn china() -> FixedOffset {
+    FixedOffset::east_opt(8 * 3600).unwrap()
+}
+
+/// 日本时间 (AtCoder)
+pub fn japan() -> FixedOffset {
+    FixedOffset::east_opt(9 * 3600).unwrap()
+}
+
+// 数字日期，支持:
+//   2024-05-01 19:00[:00]   2024/05/01 19:00   2024年05月01日 19:00
+//   05-01 19:00             5月1日 19:00       2024-05-04 21:00:00+0900
+fn numeric_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r"(?x)
+            (?-u:\b)
+            (?:(?P<year>\d{4})\s*[-/.年]\s*)?
+            (?P<month>\d{1,2})\s*[-/.月]\s*
+            (?P<day>\d{1,2})\s*日?
+            (?:\s*\([^)]*\)|\s*（[^）]*）)?
+            (?:T|\s*)
+            (?P<hour>\d{1,2})\s*[:：]\s*(?P<minute>\d{2})
+            (?:\s*[:：]\s*(?P<second>\d{2}))?
+            (?:\s*(?P<offset>Z|[+-]\d{2}:?\d{2}))?
+            ",
+        )
+        .unwrap()
+    })
+}
+
+// 英文日期，支持:
+//   May 1, 2024 19:00   May/01/2024 19:00   Sat, May 4, 2024 at 21:00   May 4 21:00
+fn english_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r"(?xi)
+            (?-u:\b)
+            (?P<mon>jan|feb|mar|apr|may|jun|jul|aug|sep|oct|nov|dec)[a-z]*\.?
+            [\s/]+(?P<day>\d{1,2})(?:st|nd|rd|th)?
+            (?:,?[\s/]+(?P<year>\d{4}))?
+            ,?\s+(?:at\s+)?
+            (?P<hour>\d{1,2}):(?P<minute>\d{2})(?::(?P<second>\d{2}))?
+            (?:\s*(?P<ampm>am|pm))?
+            (?:\s*(?P<offset>Z|[+-]\d{2}:?\d{2}|UTC|GMT))?
+            ",
+        )
+        .unwrap()
+    })
+}
+
+/// 解析 API 返回的 RFC 3339 时间, e.g. "2024-05-04T21:00:00+09:00"
+pub fn parse_iso(raw: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw.trim())
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// 在一段文本中查找第一个可识别的时间
+/// 返回解析结果及该时间在原文中结束位置的字节下标，便于继续向后查找 (如结束时间)
+pub fn find_datetime(
+    text: &str,
+    tz: FixedOffset,
+    now: DateTime<Utc>,
+) -> Option<(DateTime<Utc>, usize)> {
+    let numeric = numeric_regex()
+        .captures_iter(text)
+        .find_map(|caps| Some((from_numeric(&caps, tz, now)?, caps.get(0)?)));
+    let english = english_regex()
+        .captures_iter(text)
+        .find_map(|caps| Some((from_english(&caps, tz, now)?, caps.get(0)?)));
+
+    // 两种格式都出现时取位置靠前的
+    let (dt, m) = match (numeric, english) {
+        (Some(a), Some(b)) => {
+            if a.1.start() <= b.1.start() {
+                a
+            } else {
+                b
+            }
+        }
+        (a, b) => a.or(b)?,
+    };
+    Some((dt, m.end()))
+}
+
+/// 解析一个只包含时间的字符串 (首尾允许空白)
+pub fn parse_datetime(text: &str, tz: FixedOffset, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    let (dt, end) = find_datetime(text, tz, now)?;
+    (end == text.len()).then_some(dt)
+}
+
+fn from_numeric(caps: &Captures, tz: FixedOffset, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let month: u32 = caps.name("month")?.as_str().parse().ok()?;
+    let day: u32 = caps.name("day")?.as_str().parse().ok()?;
+    let year: Option<i32> = caps.name("year").and_then(|y| y.as_str().parse().ok());
+    build(caps, year, month, day, None, tz, now)
+}
+
+fn from_english(caps: &Captures, tz: FixedOffset, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let month = month_from_name(caps.name("mon")?.as_str())?;
+    let day: u32 = caps.name("day")?.as_str().parse().ok()?;
+    let year: Option<i32> = caps.name("year").and_then(|y| y.as_str().parse().ok());
+    let pm = caps
+        .name("ampm")
+        .map(|m| m.as_str().eq_ignore_ascii_case("pm"));
+    build(caps, year, month, day, pm, tz, now)
+}
+
+fn build(
+    caps: &Captures,
+    year: Option<i32>,
+    month: u32,
+    day: u32,
+    pm: Option<bool>,
+    tz: FixedOffset,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let mut hour: u32 = caps.name("hour")?.as_str().parse().ok()?;
+    let minute: u32 = caps.name("minute")?.as_str().parse().ok()?;
+    let second: u32 = match caps.name("second") {
+        Some(s) => s.as_str().parse().ok()?,
+        None => 0,
+    };
+
+    // 12 小时制
+    match pm {
+        Some(true) if hour < 12 => hour += 12,
+        Some(false) if hour == 12 => hour = 0,
+        _ => {}
+    }
+
+    // 文本自带时区时以文本为准
+    let tz = match caps.name("offset") {
+        Some(offset) => parse_offset(offset.as_str())?,
+        None => tz,
+    };
+
+    let to_utc = |year: i32| -> Option<DateTime<Utc>> {
+        let naive = NaiveDate::from_ymd_opt(year, month, day)?.and_hms_opt(hour, minute, second)?;
+        to_utc(naive, tz)
+    };
+
+    match year {
+        Some(y) => to_utc(y),
+        None => {
+            let current = now.with_timezone(&tz).year();
+            [current - 1, current, current + 1]
+                .into_iter()
+                .filter_map(to_utc)
+                .min_by_key(|t| (*t - now).num_seconds().abs())
+        }
+    }
+}
+
+fn to_utc(naive: NaiveDateTime, tz: FixedOffset) -> Option<DateTime<Utc>> {
+    tz.from_local_datetime(&naive)
+        .single()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+fn parse_offset(raw: &str) -> Option<FixedOffset> {
+    let upper = raw.to_ascii_uppercase();
+    if matches!(upper.as_str(), "Z" | "UTC" | "GMT") {
+        return FixedOffset::east_opt(0);
+    }
+    let sign = if raw.starts_with('-') { -1 } else { 1 };
+    let digits: String = raw[1..].chars().filter(|c| c.is_ascii_digit()).collect();
+    if digits.len() != 4 {
+        return None;
+    }
+    let hours: i32 = digits[..2].parse().ok()?;
+    let minutes: i32 = digits[2..].parse().ok()?;
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+fn month_from_name(name: &str) -> Option<u32> {
+    let idx = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ]
+    .iter()
+    .position(|m| name.to_ascii_lowercase().starts_with(m))?;
+    Some(idx as u32 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(raw: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(raw)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn missing_year_rolls_forward_in_december() {
+        let now = utc("2024-12-28T12:00:00Z");
+        let dt = parse_datetime("01-03 19:00", china(), now).unwrap();
+        assert_eq!(dt, utc("2025-01-03T11:00:00Z"));
+    }
+
+    #[test]
+    fn missing_year_rolls_back_in_january() {
+        let now = utc("2025-01-02T12:00:00Z");
+        let dt = parse_datetime("12-30 19:00", china(), now).unwrap();
+        assert_eq!(dt, utc("2024-12-30T11:00:00Z"));
+    }
+
+    #[test]
+    fn parses_chinese_date() {
+        let now = utc("2024-04-01T00:00:00Z");
+        let dt = parse_datetime("2024年05月01日 19:00", china(), now).unwrap();
+        assert_eq!(dt, utc("2024-05-01T11:00:00Z"));
+        let dt = parse_datetime("5月1日 19:00", china(), now).unwrap();
+        assert_eq!(dt, utc("2024-05-01T11:00:00Z"));
+    }
+
+    #[test]
+    fn parses_month_day_with_seconds() {
+        let now = utc("2024-04-01T00:00:00Z");
+        let dt = parse_datetime("05-01 19:00:30", china(), now).unwrap();
+        assert_eq!(dt, utc("2024-05-01T11:00:30Z"));
+    }
+
+    #[test]
+    fn offset_in_text_overrides_default() {
+        let now = utc("2024-04-01T00:00:00Z");
+        let dt = parse_datetime("2024-05-04 21:00:00+0900", china(), now).unwrap();
+        assert_eq!(dt, utc("2024-05-04T12:00:00Z"));
+        let dt = parse_datetime("2024-05-04T12:00:00Z", china(), now).unwrap();
+        assert_eq!(dt, utc("2024-05-04T12:00:00Z"));
+    }
+
+    #[test]
+    fn parses_english_with_am_pm() {
+        let now = utc("2024-04-01T00:00:00Z");
+        let dt = parse_datetime("May 4, 2024 9:00 pm", japan(), now).unwrap();
+        assert_eq!(dt, utc("2024-05-04T12:00:00Z"));
+        let dt = parse_datetime("Sat, May 4, 2024 at 12:30 AM", japan(), now).unwrap();
+        assert_eq!(dt, utc("2024-05-03T15:30:00Z"));
+    }
+
+    #[test]
+    fn applies_site_offsets() {
+        let now = utc("2024-04-01T00:00:00Z");
+        let cn = parse_datetime("2024-05-01 08:00", china(), now).unwrap();
+        let jp = parse_datetime("2024-05-01 08:00", japan(), now).unwrap();
+        assert_eq!(cn, utc("2024-05-01T00:00:00Z"));
+        assert_eq!(jp, utc("2024-04-30T23:00:00Z"));
+    }
+
+    #[test]
+    fn find_returns_end_of_match() {
+        let now = utc("2024-04-01T00:00:00Z");
+        let text = "比赛时间：2024-05-01 19:00 至 2024-05-01 21:00";
+        let (start, end) = find_datetime(text, china(), now).unwrap();
+        assert_eq!(start, utc("2024-05-01T11:00:00Z"));
+        let (finish, _) = find_datetime(&text[end..], china(), now).unwrap();
+        assert_eq!(finish, utc("2024-05-01T13:00:00Z"));
+    }
+
+    #[test]
+    fn parses_iso_timestamps() {
+        let dt = parse_iso("2024-05-04T21:00:00+09:00").unwrap();
+        assert_eq!(dt, utc("2024-05-04T12:00:00Z"));
+        assert!(parse_iso("2024-05-04 21:00").is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        let now = utc("2024-04-01T00:00:00Z");
+        assert!(parse_datetime("", china(), now).is_none());
+        assert!(parse_datetime("即将开始", china(), now).is_none());
+        assert!(parse_datetime("2024-13-01 19:00", china(), now).is_none());
+        assert!(parse_datetime("2024-02-30 19:00", china(), now).is_none());
+        assert!(parse_datetime("2024-05-01 25:00", china(), now).is_none());
+        assert!(parse_datetime("2024-05-01 19:00 之后", china(), now).is_none());
+    }
+}
@@ -0,0 +1,142 @@
+// 文件路径: src-tauri/src/tray.rs
+//
+// 系统托盘：菜单列出缓存中最近的几场比赛及剩余时间，点击在浏览器中打开比赛页面；
+// 另有 "立即刷新" / "显示窗口" / "退出"。关闭主窗口时只是隐藏到托盘 (见 lib.rs)，
+// 后台提醒照常运行，需要真正退出时使用托盘菜单。
+
+use crate::cache;
+use crate::models::Contest;
+use crate::refresh;
+use crate::reminders;
+use anyhow::Result;
+use chrono::Utc;
+use tauri::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_shell::ShellExt;
+
+const TRAY_ID: &str = "main";
+// 菜单中最多列出的比赛数
+const MAX_CONTESTS: usize = 5;
+// 比赛名过长时截断，避免菜单过宽
+const MAX_NAME_CHARS: usize = 36;
+// 剩余时间随时间变化，定期重建菜单
+const REFRESH_INTERVAL_SECS: u64 = 60;
+
+// 比赛菜单项的 id 前缀，其后为比赛链接
+const CONTEST_ID_PREFIX: &str = "contest:";
+
+/// 创建托盘图标并启动定时刷新菜单的任务
+pub fn init(app: &AppHandle) -> Result<()> {
+    let menu = build_menu(app)?;
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .menu(&menu)
+        .tooltip("CPC Helper")
+        .show_menu_on_left_click(true)
+        .on_menu_event(handle_menu_event);
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(REFRESH_INTERVAL_SECS)).await;
+            refresh_menu(&app);
+        }
+    });
+    Ok(())
+}
+
+/// 托盘是否创建成功 (部分 Linux 桌面缺少 appindicator 支持)
+pub fn is_available(app: &AppHandle) -> bool {
+    app.tray_by_id(TRAY_ID).is_some()
+}
+
+/// 按当前缓存重建托盘菜单，比赛列表更新后调用
+pub fn refresh_menu(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    if let Ok(menu) = build_menu(app) {
+        let _ = tray.set_menu(Some(menu));
+    }
+}
+
+fn build_menu(app: &AppHandle) -> Result<Menu<tauri::Wry>> {
+    let menu = Menu::new(app)?;
+    let item = |id: &str, text: &str, enabled: bool| {
+        MenuItem::with_id(app, id, text, enabled, None::<&str>)
+    };
+
+    let now = Utc::now();
+    let upcoming: Vec<Contest> = cache::contests()
+        .into_iter()
+        .filter(|c| c.end_time.unwrap_or(c.start_time) > now)
+        .take(MAX_CONTESTS)
+        .collect();
+
+    if upcoming.is_empty() {
+        menu.append(&item("empty", "暂无即将开始的比赛", false)?)?;
+    }
+    for contest in &upcoming {
+        let remaining = contest.start_time - now;
+        let status = if remaining.num_seconds() > 0 {
+            reminders::format_remaining(remaining)
+        } else {
+            "进行中".to_string()
+        };
+        let text = format!(
+            "{} {} · {}",
+            contest.platform,
+            truncate(&contest.name),
+            status
+        );
+        let id = format!("{}{}", CONTEST_ID_PREFIX, contest.url);
+        menu.append(&item(&id, &text, true)?)?;
+    }
+
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&item("refresh", "立即刷新", true)?)?;
+    menu.append(&item("show", "显示窗口", true)?)?;
+    menu.append(&item("quit", "退出", true)?)?;
+    Ok(menu)
+}
+
+fn handle_menu_event(app: &AppHandle, event: MenuEvent) {
+    match event.id().as_ref() {
+        "refresh" => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                // 列表有变化时 refresh.rs 会重建菜单
+                refresh::refresh_all(&app, cache::fetch_options()).await;
+            });
+        }
+        "show" => show_main_window(app),
+        "quit" => app.exit(0),
+        id => {
+            if let Some(url) = id.strip_prefix(CONTEST_ID_PREFIX) {
+                // 与前端一致，通过 shell 插件交给系统浏览器打开
+                #[allow(deprecated)]
+                let _ = app.shell().open(url, None);
+            }
+        }
+    }
+}
+
+fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+fn truncate(name: &str) -> String {
+    if name.chars().count() <= MAX_NAME_CHARS {
+        return name.to_string();
+    }
+    let head: String = name.chars().take(MAX_NAME_CHARS - 1).collect();
+    format!("{}…", head)
+}
@@ -0,0 +1,295 @@
+// 文件路径: src-tauri/src/webhooks.rs
+//
+// 提醒与每日赛程的 Webhook 推送。按 WebhookKind 生成各聊天平台要求的请求体，
+// 消息正文由模板渲染，模板中可使用以下占位符:
+//   {platform} {name} {url} {start} {end} {remaining} {category} {format}
+// URL 可以指向任意地址 (包括本地的测试服务)，便于调试模板与请求体。
+// 自动推送在后台进行，失败时按 URL 记下最近一次的错误，供设置页展示。
+
+use crate::models::{Contest, WebhookConfig, WebhookFailure, WebhookKind};
+use crate::reminders::format_remaining;
+use anyhow::{anyhow, Result};
+use chrono::{Duration, Local, Utc};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+const DEFAULT_TEMPLATE: &str = "【{platform}】{name}\n{remaining} 后开始 ({start})\n{url}";
+
+// Webhook URL -> 最近一次推送失败的记录
+static FAILURES: Mutex<BTreeMap<String, WebhookFailure>> = Mutex::new(BTreeMap::new());
+
+/// 各 Webhook 最近一次自动推送失败的记录
+pub fn failures() -> Vec<WebhookFailure> {
+    FAILURES.lock().unwrap().values().cloned().collect()
+}
+
+/// 用比赛字段渲染模板
+pub fn render(template: &str, contest: &Contest, remaining: Duration) -> String {
+    let fmt_time =
+        |t: chrono::DateTime<Utc>| t.with_timezone(&Local).format("%m-%d %H:%M").to_string();
+
+    template
+        .replace("{platform}", &contest.platform)
+        .replace("{name}", &contest.name)
+        .replace("{url}", &contest.url)
+        .replace("{start}", &fmt_time(contest.start_time))
+        .replace("{end}", &contest.end_time.map(fmt_time).unwrap_or_default())
+        .replace("{remaining}", &format_remaining(remaining))
+        .replace(
+            "{category}",
+            contest.category.as_deref().unwrap_or_default(),
+        )
+        .replace("{format}", contest.format.as_deref().unwrap_or_default())
+}
+
+/// 按类型生成请求体；contests 只在 Generic 类型中原样附带
+fn payload(hook: &WebhookConfig, text: &str, contests: &[Contest]) -> Result<Value> {
+    Ok(match hook.kind {
+        WebhookKind::Generic => json!({ "text": text, "contests": contests }),
+        WebhookKind::Discord => json!({ "content": text }),
+        WebhookKind::Slack => json!({ "text": text }),
+        WebhookKind::Feishu => json!({ "msg_type": "text", "content": { "text": text } }),
+        WebhookKind::DingTalk => json!({ "msgtype": "text", "text": { "content": text } }),
+        WebhookKind::Telegram => {
+            let chat_id = hook
+                .chat_id
+                .as_deref()
+                .filter(|id| !id.trim().is_empty())
+                .ok_or_else(|| anyhow!("Telegram webhook '{}' requires chat_id", hook.name))?;
+            json!({ "chat_id": chat_id, "text": text, "disable_web_page_preview": true })
+        }
+    })
+}
+
+/// 发送一条消息，非 2xx 视为失败
+pub async fn send(hook: &WebhookConfig, text: &str, contests: &[Contest]) -> Result<()> {
+    let body = payload(hook, text, contests)?;
+    let resp = reqwest::Client::new()
+        .post(&hook.url)
+        .json(&body)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        return Err(anyhow!(
+            "Webhook '{}' returned status {}",
+            hook.name,
+            resp.status()
+        ));
+    }
+    Ok(())
+}
+
+// 自动推送：发送并记录结果
+async fn deliver(hook: &WebhookConfig, text: &str, contests: &[Contest]) {
+    let res = send(hook, text, contests).await;
+    let mut failures = FAILURES.lock().unwrap();
+    match res {
+        Ok(()) => {
+            failures.remove(&hook.url);
+        }
+        Err(e) => {
+            failures.insert(
+                hook.url.clone(),
+                WebhookFailure {
+                    name: hook.name.clone(),
+                    url: hook.url.clone(),
+                    error: e.to_string(),
+                    time: Utc::now(),
+                },
+            );
+        }
+    }
+}
+
+/// 单场比赛的提醒，推送到全部启用的 Webhook；单个失败不影响其他
+pub async fn broadcast_reminder(hooks: &[WebhookConfig], contest: &Contest, remaining: Duration) {
+    for hook in hooks.iter().filter(|h| h.enabled) {
+        let template = hook.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+        let text = render(template, contest, remaining);
+        deliver(hook, &text, std::slice::from_ref(contest)).await;
+    }
+}
+
+/// 每日赛程：每场比赛按模板渲染后拼接
+pub async fn broadcast_digest(hooks: &[WebhookConfig], contests: &[Contest]) {
+    let now = Utc::now();
+    for hook in hooks.iter().filter(|h| h.enabled) {
+        deliver(hook, &digest_text(hook, contests, now), contests).await;
+    }
+}
+
+fn digest_text(hook: &WebhookConfig, contests: &[Contest], now: chrono::DateTime<Utc>) -> String {
+    let template = hook.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+    let mut text = format!("未来 24 小时共 {} 场比赛", contests.len());
+    for contest in contests {
+        text.push_str("\n\n");
+        text.push_str(&render(template, contest, contest.start_time - now));
+    }
+    text
+}
+
+/// 发送一条示例消息，用于在设置页检查配置
+pub async fn send_test(hook: &WebhookConfig) -> Result<()> {
+    let now = Utc::now();
+    let sample = Contest {
+        name: "Test Round".to_string(),
+        start_time: now + Duration::hours(1),
+        end_time: Some(now + Duration::hours(3)),
+        url: "https://example.com/contest/1".to_string(),
+        platform: "CPC Helper".to_string(),
+        ..Default::default()
+    };
+    let template = hook.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+    let text = render(template, &sample, Duration::hours(1));
+    send(hook, &text, std::slice::from_ref(&sample)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    // 本地 HTTP 服务：接收一个请求，以 status 应答，返回请求体
+    async fn serve_once(status: &'static str) -> (String, tokio::task::JoinHandle<Value>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            let body_start = loop {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                if let Some(pos) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break pos + 4;
+                }
+            };
+            let head = String::from_utf8_lossy(&request[..body_start]).to_ascii_lowercase();
+            assert!(head.starts_with("post /hook "));
+            let length: usize = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .map(|v| v.trim().parse().unwrap())
+                .unwrap();
+            while request.len() < body_start + length {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                status
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            serde_json::from_slice(&request[body_start..body_start + length]).unwrap()
+        });
+        (url, handle)
+    }
+
+    fn hook(kind: WebhookKind, url: &str) -> WebhookConfig {
+        WebhookConfig {
+            name: "test".to_string(),
+            kind,
+            url: url.to_string(),
+            enabled: true,
+            chat_id: Some("42".to_string()),
+            template: None,
+        }
+    }
+
+    fn sample() -> Contest {
+        Contest {
+            name: "Round 1".to_string(),
+            start_time: Utc::now() + Duration::hours(1),
+            url: "https://example.com/contest/1".to_string(),
+            platform: "Codeforces".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn posts_payload_for_each_kind() {
+        let contest = sample();
+        let text = "【Codeforces】Round 1";
+        let cases = [
+            (
+                WebhookKind::Generic,
+                json!({ "text": text, "contests": [&contest] }),
+            ),
+            (WebhookKind::Discord, json!({ "content": text })),
+            (WebhookKind::Slack, json!({ "text": text })),
+            (
+                WebhookKind::Feishu,
+                json!({ "msg_type": "text", "content": { "text": text } }),
+            ),
+            (
+                WebhookKind::DingTalk,
+                json!({ "msgtype": "text", "text": { "content": text } }),
+            ),
+            (
+                WebhookKind::Telegram,
+                json!({ "chat_id": "42", "text": text, "disable_web_page_preview": true }),
+            ),
+        ];
+
+        for (kind, expected) in cases {
+            let (url, server) = serve_once("200 OK").await;
+            send(&hook(kind, &url), text, std::slice::from_ref(&contest))
+                .await
+                .unwrap();
+            assert_eq!(server.await.unwrap(), expected, "{:?}", kind);
+        }
+    }
+
+    #[tokio::test]
+    async fn records_and_clears_broadcast_failures() {
+        let contest = sample();
+
+        let (url, server) = serve_once("500 Internal Server Error").await;
+        let hooks = [hook(WebhookKind::Slack, &url)];
+        broadcast_reminder(&hooks, &contest, Duration::hours(1)).await;
+        server.await.unwrap();
+        let failure = failures().into_iter().find(|f| f.url == url).unwrap();
+        assert!(failure.error.contains("500"));
+
+        // 再次推送成功后清除该地址的记录
+        let (url2, server) = serve_once("200 OK").await;
+        let mut retry = hooks[0].clone();
+        retry.url = url2.clone();
+        FAILURES.lock().unwrap().insert(
+            url2.clone(),
+            WebhookFailure {
+                url: url2.clone(),
+                ..failure
+            },
+        );
+        broadcast_reminder(&[retry], &contest, Duration::hours(1)).await;
+        server.await.unwrap();
+        assert!(!failures().iter().any(|f| f.url == url2));
+    }
+
+    #[test]
+    fn telegram_requires_chat_id() {
+        let mut telegram = hook(WebhookKind::Telegram, "http://127.0.0.1/");
+        telegram.chat_id = None;
+        assert!(payload(&telegram, "text", &[]).is_err());
+    }
+
+    #[test]
+    fn renders_template_placeholders() {
+        let mut contest = sample();
+        contest.category = Some("Div. 2".to_string());
+        let text = render(
+            "{platform}|{name}|{url}|{category}|{format}",
+            &contest,
+            Duration::hours(1),
+        );
+        assert_eq!(
+            text,
+            "Codeforces|Round 1|https://example.com/contest/1|Div. 2|"
+        );
+    }
+}
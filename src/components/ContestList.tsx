@@ -1,7 +1,7 @@
 import { useState, useEffect } from 'react';
-import { Contest } from '../types';
-import { fetchAllContests } from '../services/contestService';
-import { getPlatformColor, formatTime, formatDate } from '../utils';
+import { Contest, ContestChange, ReminderSettings } from '../types';
+import { fetchAllContests, getContestChanges, getReminderSettings, onContestsUpdated, setReminderSettings } from '../services/contestService';
+import { getPlatformColor, formatTime, formatDate, formatShift, formatRatedRange, isRatedFor } from '../utils';
 import { open } from '@tauri-apps/plugin-shell';
 import { RefreshIcon, ExternalLinkIcon } from './Icons';
 
@@ -26,6 +26,10 @@ const ContestList = ({ cardStyle }: ContestListProps) => {
   const [error, setError] = useState<string | null>(null);
   //简单的反馈状态
   const [addedIds, setAddedIds] = useState<Set<string>>(new Set());
+  // [新增] 提醒订阅 (后端调度)
+  const [reminders, setReminders] = useState<ReminderSettings | null>(null);
+  // [新增] 比赛变更记录 (最新的在前)，用于标注改期 / 改名
+  const [changes, setChanges] = useState<ContestChange[]>([]);
 
   const loadContests = async () => {
     setLoading(true);
@@ -33,6 +37,7 @@ const ContestList = ({ cardStyle }: ContestListProps) => {
     try {
       const data = await fetchAllContests();
       setContests(data);
+      getContestChanges().then(setChanges).catch(console.error);
     } catch (err) {
       setError('Failed to fetch contests.');
     } finally {
@@ -42,8 +47,38 @@ const ContestList = ({ cardStyle }: ContestListProps) => {
 
   useEffect(() => {
     loadContests();
+    getReminderSettings().then(setReminders).catch(console.error);
   }, []);
 
+  // [新增] 后台自动刷新发现变化时直接替换列表
+  useEffect(() => {
+    const unlisten = onContestsUpdated((update) => {
+      setContests(update.contests);
+      if (update.changes.length > 0) setChanges((prev) => [...update.changes.slice().reverse(), ...prev]);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // [新增] 切换单场比赛的提醒订阅
+  const handleToggleReminder = async (contest: Contest) => {
+    if (!reminders) return;
+    const subscribed = reminders.contests.includes(contest.url);
+    const next: ReminderSettings = {
+      ...reminders,
+      contests: subscribed
+        ? reminders.contests.filter((url) => url !== contest.url)
+        : [...reminders.contests, contest.url],
+    };
+    setReminders(next);
+    try {
+      await setReminderSettings(next);
+    } catch (e) {
+      console.error('Failed to save reminder settings', e);
+    }
+  };
+
   const handleOpenLink = async (url: string) => {
     try {
       await open(url);
@@ -128,6 +163,18 @@ const ContestList = ({ cardStyle }: ContestListProps) => {
         ) : (
           contests.map((contest, index) => {
              const isAdded = addedIds.has(contest.url);
+             // 单场订阅可在此切换；整个平台的订阅只能在设置里取消
+             const platformReminded = !!reminders &&
+               reminders.platforms.some((p) => p.toLowerCase() === contest.platform.toLowerCase());
+             const isReminded = platformReminded || (!!reminders && reminders.contests.includes(contest.url));
+             // [新增] 最近一次改期 / 改名
+             const moved = changes.find((c) => c.contest_id === contest.url && c.kind === 'rescheduled');
+             const renamed = changes.find((c) => c.contest_id === contest.url && c.kind === 'renamed');
+             // [新增] 按卡片里查询到的 Rating 判断是否计分 (目前只有 AtCoder 提供 rated_range)
+             const savedRating = localStorage.getItem(`cpc_rating_${contest.platform.toLowerCase()}`);
+             const rated = contest.rated_range && savedRating != null
+               ? isRatedFor(contest.rated_range, Number(savedRating))
+               : null;
              return (
               <div 
                 key={`${contest.platform}-${index}`}
@@ -152,6 +199,34 @@ const ContestList = ({ cardStyle }: ContestListProps) => {
                       <span className="text-gray-400 text-xs font-mono">
                         {formatDate(contest.start_time)} {formatTime(contest.start_time)}
                       </span>
+                      {moved && (
+                        <span
+                          className="text-[10px] font-bold px-1.5 py-0.5 rounded bg-amber-500/20 text-amber-300 border border-amber-500/30"
+                          title={moved.old_start_time ? `原定 ${formatDate(moved.old_start_time)} ${formatTime(moved.old_start_time)}` : undefined}
+                        >
+                          {formatShift(moved.shift_minutes ?? 0)}
+                        </span>
+                      )}
+                      {renamed && (
+                        <span
+                          className="text-[10px] font-bold px-1.5 py-0.5 rounded bg-white/10 text-gray-300 border border-white/10"
+                          title={`原名: ${renamed.old_name ?? ''}`}
+                        >
+                          已改名
+                        </span>
+                      )}
+                      {contest.rated_range && (
+                        <span
+                          className={`text-[10px] font-bold px-1.5 py-0.5 rounded border ${
+                            rated === false
+                              ? 'bg-white/5 text-gray-500 border-white/10'
+                              : 'bg-emerald-500/20 text-emerald-300 border-emerald-500/30'
+                          }`}
+                          title={`Rated: ${formatRatedRange(contest.rated_range)}`}
+                        >
+                          {rated === null ? `Rated ${formatRatedRange(contest.rated_range)}` : rated ? 'Rated' : 'Unrated'}
+                        </span>
+                      )}
                     </div>
                     <h3 
                       className="text-base font-semibold text-white/90 leading-tight cursor-pointer hover:text-blue-300 transition-colors line-clamp-2"
@@ -171,6 +246,25 @@ const ContestList = ({ cardStyle }: ContestListProps) => {
                     >
                       <ExternalLinkIcon />
                     </button>
+                    {/* [新增] 提醒订阅按钮 */}
+                    <button
+                      onClick={() => handleToggleReminder(contest)}
+                      className={`p-1.5 rounded transition-colors ${
+                        platformReminded
+                          ? 'text-yellow-400 bg-yellow-400/10 cursor-default'
+                          : isReminded
+                          ? 'text-yellow-400 hover:text-yellow-300 bg-yellow-400/10'
+                          : 'text-gray-400 hover:text-yellow-300 hover:bg-white/10'
+                      }`}
+                      title={
+                        platformReminded
+                          ? `已订阅整个 ${contest.platform} 平台，请在设置中取消`
+                          : isReminded ? 'Reminder On' : 'Remind Me'
+                      }
+                      disabled={platformReminded}
+                    >
+                      <svg xmlns="http://www.w3.org/2000/svg" width="16" height="16" viewBox="0 0 24 24" fill={isReminded ? 'currentColor' : 'none'} stroke="currentColor" strokeWidth="2" strokeLinecap="round" strokeLinejoin="round"><path d="M18 8A6 6 0 0 0 6 8c0 7-3 9-3 9h18s-3-2-3-9"></path><path d="M13.73 21a2 2 0 0 1-3.46 0"></path></svg>
+                    </button>
                     {/* [新增] 添加日程按钮 */}
                     <button 
                       onClick={() => handleAddToSchedule(contest)}
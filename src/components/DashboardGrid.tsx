@@ -1,6 +1,6 @@
 import { useState, useEffect, forwardRef, useImperativeHandle } from 'react';
 import { UserStats } from '../types';
-import { fetchUserStats } from '../services/contestService';
+import { fetchUserStats, onStatsUpdated, syncRefreshAccounts } from '../services/contestService';
 import { getPlatformColor, getRatingColor } from '../utils';
 import { SearchIcon } from './Icons';
 
@@ -19,6 +19,11 @@ const OJ_LINKS: Record<string, { home: string; profile: string }> = {
     profile: 'https://bs.daimayuan.top/user/' 
   },
   hdu: { home: 'https://acm.hdu.edu.cn', profile: 'https://acm.hdu.edu.cn/userstatus.php?user=' },
+  codechef: { home: 'https://www.codechef.com', profile: 'https://www.codechef.com/users/' },
+  yukicoder: { home: 'https://yukicoder.me', profile: 'https://yukicoder.me/users/' },
+  libreoj: { home: 'https://loj.ac', profile: 'https://loj.ac/u/' },
+  uoj: { home: 'https://uoj.ac', profile: 'https://uoj.ac/user/profile/' },
+  qoj: { home: 'https://qoj.ac', profile: 'https://qoj.ac/user/profile/' },
 };
 
 interface PlatformCardProps {
@@ -33,6 +38,11 @@ export interface PlatformCardRef {
   triggerSearch: () => Promise<void>;
 }
 
+// 记下当前 Rating，比赛列表据此判断 AtCoder 等比赛是否计分
+const saveRating = (platformKey: string, stats: UserStats) => {
+  if (stats.rating != null) localStorage.setItem(`cpc_rating_${platformKey}`, String(stats.rating));
+};
+
 const PlatformCard = forwardRef<PlatformCardRef, PlatformCardProps>(
   ({ platformName, platformKey, cardStyle, isEnabled = false, onStatsUpdate }, ref) => {
     const [handle, setHandle] = useState('');
@@ -45,6 +55,21 @@ const PlatformCard = forwardRef<PlatformCardRef, PlatformCardProps>(
       if (saved) setHandle(saved);
     }, [platformKey]);
 
+    // [新增] 后台自动刷新推送的战绩
+    useEffect(() => {
+      const unlisten = onStatsUpdated((update) => {
+        if (update.platform.toLowerCase() !== platformKey) return;
+        if (update.handle !== localStorage.getItem(`cpc_handle_${platformKey}`)) return;
+        setStats(update.stats);
+        setError(null);
+        saveRating(platformKey, update.stats);
+        if (onStatsUpdate) onStatsUpdate(platformKey, update.stats.solved_count || 0);
+      });
+      return () => {
+        unlisten.then((fn) => fn());
+      };
+    }, [platformKey, onStatsUpdate]);
+
     const handleSearch = async () => {
       if (!isEnabled || !handle.trim()) return;
       
@@ -54,10 +79,12 @@ const PlatformCard = forwardRef<PlatformCardRef, PlatformCardProps>(
       if (onStatsUpdate) onStatsUpdate(platformKey, 0);
 
       localStorage.setItem(`cpc_handle_${platformKey}`, handle);
+      syncRefreshAccounts().catch(console.error);
 
       try {
         const data = await fetchUserStats(platformKey, handle);
         setStats(data);
+        saveRating(platformKey, data);
         if (onStatsUpdate) {
           onStatsUpdate(platformKey, data.solved_count || 0);
         }
@@ -1,4 +1,6 @@
 import { useState, useEffect } from 'react';
+import { getRefreshSettings, getReminderSettings, getScraperErrors, getWebhookFailures, setRefreshSettings, setReminderSettings, syncRefreshAccounts, testWebhook, validateNowCoderCookie } from '../services/contestService';
+import { RefreshSettings, ReminderSettings, ScraperError, WebhookConfig, WebhookFailure, WebhookKind } from '../types';
 // import { XIcon } from './Icons'; // 如果你没有这个组件，可以注释掉
 
 interface SettingsDrawerProps {
@@ -21,7 +23,97 @@ const SettingsDrawer = ({
   defaultConfig,
 }: SettingsDrawerProps) => {
   const [ncCookie, setNcCookie] = useState('');
+  const [lgCookie, setLgCookie] = useState(() => localStorage.getItem('luogu_cookie') || '');
   const [cookieStatus, setCookieStatus] = useState('');
+  const [lcRegion, setLcRegion] = useState(() => localStorage.getItem('leetcode_region') || 'cn');
+  const [clistUser, setClistUser] = useState(() => localStorage.getItem('clist_username') || '');
+  const [clistKey, setClistKey] = useState(() => localStorage.getItem('clist_api_key') || '');
+  // [新增] 无法加载的自定义爬虫定义
+  const [scraperErrors, setScraperErrors] = useState<ScraperError[]>([]);
+
+  useEffect(() => {
+    if (!isOpen) return;
+    getScraperErrors().then(setScraperErrors).catch(console.error);
+  }, [isOpen]);
+
+  // [新增] 提醒设置 (保存在后端)
+  const [reminders, setReminders] = useState<ReminderSettings | null>(null);
+  const [leadText, setLeadText] = useState('');
+  const [platformText, setPlatformText] = useState('');
+  // 后台推送失败的 Webhook (url -> 最近一次失败)
+  const [hookFailures, setHookFailures] = useState<Record<string, WebhookFailure>>({});
+
+  useEffect(() => {
+    if (!isOpen) return;
+    getReminderSettings()
+      .then((s) => {
+        setReminders(s);
+        setLeadText(s.lead_minutes.join(', '));
+        setPlatformText(s.platforms.join(', '));
+      })
+      .catch(console.error);
+    getWebhookFailures()
+      .then((list) => setHookFailures(Object.fromEntries(list.map((f) => [f.url, f]))))
+      .catch(console.error);
+  }, [isOpen]);
+
+  // [新增] 后台自动刷新设置；平台间隔以 "Codeforces=10, clist=60" 的形式编辑
+  const [refresh, setRefresh] = useState<RefreshSettings | null>(null);
+  const [intervalText, setIntervalText] = useState('');
+
+  useEffect(() => {
+    if (!isOpen) return;
+    getRefreshSettings()
+      .then((s) => {
+        setRefresh(s);
+        setIntervalText(Object.entries(s.platform_intervals).map(([k, v]) => `${k}=${v}`).join(', '));
+      })
+      .catch(console.error);
+  }, [isOpen]);
+
+  const handleSaveRefresh = async () => {
+    if (!refresh) return;
+    const platform_intervals: Record<string, number> = {};
+    for (const item of intervalText.split(/[,，]+/)) {
+      const [name, minutes] = item.split('=').map((x) => x.trim());
+      if (name && minutes && Number.isFinite(Number(minutes))) platform_intervals[name] = Number(minutes);
+    }
+    const next: RefreshSettings = { ...refresh, platform_intervals };
+    await setRefreshSettings(next);
+    setRefresh(next);
+    // Cookie 可能刚被修改，重新同步账号
+    await syncRefreshAccounts();
+  };
+
+  // [新增] Webhook 编辑 (新增一条、测试、删除)
+  const [hookDraft, setHookDraft] = useState<WebhookConfig>({ name: '', kind: 'generic', url: '', enabled: true });
+  const [hookStatus, setHookStatus] = useState('');
+
+  const updateHooks = (webhooks: WebhookConfig[]) => {
+    if (reminders) setReminders({ ...reminders, webhooks });
+  };
+
+  const handleTestHook = async (hook: WebhookConfig) => {
+    setHookStatus('发送中...');
+    try {
+      await testWebhook(hook);
+      setHookStatus(`${hook.name || hook.kind}: 发送成功`);
+    } catch (e) {
+      setHookStatus(`${hook.name || hook.kind}: ${e}`);
+    }
+  };
+
+  const handleSaveReminders = async () => {
+    if (!reminders) return;
+    const split = (text: string) => text.split(/[,，]+/).map((x) => x.trim()).filter(Boolean);
+    const next: ReminderSettings = {
+      ...reminders,
+      lead_minutes: split(leadText).map(Number).filter((n) => Number.isInteger(n) && n > 0 && n <= 7 * 24 * 60),
+      platforms: split(platformText),
+    };
+    await setReminderSettings(next);
+    setReminders(next);
+  };
 
   // 组件加载时读取保存的 Cookie
   useEffect(() => {
@@ -30,26 +122,60 @@ const SettingsDrawer = ({
     if (saved) setNcCookie(saved);
   }, []);
 
-  // 保存 Cookie 到 LocalStorage
+  // 保存 Cookie 到 LocalStorage，并一起保存提醒 / Webhook / 自动刷新设置
   const handleSaveCookie = () => {
     console.log('[SettingsDrawer] 正在保存 Cookie...');
     console.log('[SettingsDrawer] 内容长度:', ncCookie.length);
     
-    if (!ncCookie.trim()) {
-        alert("Cookie 内容为空，无法保存！");
-        return;
+    // 洛谷 Cookie 为选填项，清空即退出登录模式
+    if (lgCookie.trim()) {
+      localStorage.setItem('luogu_cookie', lgCookie);
+    } else {
+      localStorage.removeItem('luogu_cookie');
     }
 
-    localStorage.setItem('nowcoder_cookie', ncCookie);
-    
-    // 立即读取验证
-    const verify = localStorage.getItem('nowcoder_cookie');
-    console.log('[SettingsDrawer] 保存后立即验证:', verify === ncCookie ? '成功' : '失败');
+    // clist.by 账号为选填项，两项都填写才会启用
+    if (clistUser.trim() && clistKey.trim()) {
+      localStorage.setItem('clist_username', clistUser.trim());
+      localStorage.setItem('clist_api_key', clistKey.trim());
+    } else {
+      localStorage.removeItem('clist_username');
+      localStorage.removeItem('clist_api_key');
+    }
 
-    setCookieStatus('已保存!');
+    // NowCoder Cookie 为空时只跳过它本身，其余设置照常保存
+    if (ncCookie.trim()) {
+      localStorage.setItem('nowcoder_cookie', ncCookie);
+
+      // 立即读取验证
+      const verify = localStorage.getItem('nowcoder_cookie');
+      console.log('[SettingsDrawer] 保存后立即验证:', verify === ncCookie ? '成功' : '失败');
+    }
+
+    // Cookie 写入之后再保存，自动刷新会按最新的 Cookie 同步账号
+    handleSaveReminders().catch(console.error);
+    handleSaveRefresh().catch(console.error);
+
+    setCookieStatus(ncCookie.trim() ? '已保存!' : '已保存 (NowCoder Cookie 为空，未保存)');
     setTimeout(() => setCookieStatus(''), 2000);
   };
 
+  // 检查 NowCoder Cookie 是否仍处于登录态
+  const handleValidateCookie = async () => {
+    setCookieStatus('检查中...');
+    try {
+      const session = await validateNowCoderCookie(ncCookie);
+      if (session.logged_in) {
+        const expires = session.expires_at ? `，约 ${new Date(session.expires_at).toLocaleDateString()} 过期` : '';
+        setCookieStatus(`已登录 (uid ${session.uid})${expires}`);
+      } else {
+        setCookieStatus('Cookie 已失效');
+      }
+    } catch (e) {
+      setCookieStatus(`检查失败: ${e}`);
+    }
+  };
+
   return (
     <>
       {/* 遮罩层 */}
@@ -137,8 +263,218 @@ const SettingsDrawer = ({
               />
             </div>
 
+            <div className="mb-4">
+              <label className="block text-sm text-gray-300 mb-2 flex items-center justify-between">
+                Luogu Cookie
+                <span className="text-[10px] bg-white/5 text-gray-400 px-1.5 py-0.5 rounded border border-white/10">选填</span>
+              </label>
+
+              <textarea
+                rows={3}
+                value={lgCookie}
+                onChange={(e) => setLgCookie(e.target.value)}
+                placeholder="__client_id=...; _uid=..."
+                className="w-full bg-black/30 border border-white/10 rounded-lg p-3 text-[11px] text-gray-300 font-mono focus:border-blue-500/50 outline-none resize-none"
+              />
+            </div>
+
+            <div className="mb-4">
+              <label className="block text-sm text-gray-300 mb-2">LeetCode 站点</label>
+              <select
+                value={lcRegion}
+                onChange={(e) => {
+                  setLcRegion(e.target.value);
+                  localStorage.setItem('leetcode_region', e.target.value);
+                }}
+                className="w-full bg-black/30 border border-white/10 rounded-lg px-3 py-2 text-sm text-gray-300 focus:border-blue-500/50 outline-none"
+              >
+                <option value="cn">国服 (leetcode.cn)</option>
+                <option value="com">国际服 (leetcode.com)</option>
+              </select>
+            </div>
+
+            <div className="mb-4">
+              <label className="block text-sm text-gray-300 mb-2 flex items-center justify-between">
+                clist.by API
+                <span className="text-[10px] bg-white/5 text-gray-400 px-1.5 py-0.5 rounded border border-white/10">选填</span>
+              </label>
+              <input
+                type="text"
+                value={clistUser}
+                onChange={(e) => setClistUser(e.target.value)}
+                placeholder="username"
+                className="w-full mb-2 bg-black/30 border border-white/10 rounded-lg px-3 py-2 text-[11px] text-gray-300 font-mono focus:border-blue-500/50 outline-none"
+              />
+              <input
+                type="password"
+                value={clistKey}
+                onChange={(e) => setClistKey(e.target.value)}
+                placeholder="api_key"
+                className="w-full bg-black/30 border border-white/10 rounded-lg px-3 py-2 text-[11px] text-gray-300 font-mono focus:border-blue-500/50 outline-none"
+              />
+            </div>
+
+            {scraperErrors.length > 0 && (
+              <div className="mb-4 space-y-1 text-[11px] text-red-400">
+                <div>以下自定义爬虫定义无法加载 (scrapers/):</div>
+                {scraperErrors.map((e) => (
+                  <div key={e.file} className="truncate" title={e.error}>{e.file}: {e.error}</div>
+                ))}
+              </div>
+            )}
+
+            {reminders && (
+              <div className="mb-4">
+                <label className="block text-sm text-gray-300 mb-2 flex items-center justify-between">
+                  比赛提醒
+                  <input
+                    type="checkbox"
+                    checked={reminders.enabled}
+                    onChange={(e) => setReminders({ ...reminders, enabled: e.target.checked })}
+                    className="accent-blue-500"
+                  />
+                </label>
+                <input
+                  type="text"
+                  value={leadText}
+                  onChange={(e) => setLeadText(e.target.value)}
+                  placeholder="提前量 (分钟), 如 1440, 60, 10"
+                  className="w-full mb-2 bg-black/30 border border-white/10 rounded-lg px-3 py-2 text-[11px] text-gray-300 font-mono focus:border-blue-500/50 outline-none"
+                />
+                <input
+                  type="text"
+                  value={platformText}
+                  onChange={(e) => setPlatformText(e.target.value)}
+                  placeholder="订阅整个平台, 如 Codeforces, AtCoder"
+                  className="w-full mb-2 bg-black/30 border border-white/10 rounded-lg px-3 py-2 text-[11px] text-gray-300 font-mono focus:border-blue-500/50 outline-none"
+                />
+                <input
+                  type="text"
+                  value={reminders.daily_digest ?? ''}
+                  onChange={(e) => setReminders({ ...reminders, daily_digest: e.target.value.trim() || null })}
+                  placeholder="每日赛程推送时间, 如 08:00"
+                  className="w-full bg-black/30 border border-white/10 rounded-lg px-3 py-2 text-[11px] text-gray-300 font-mono focus:border-blue-500/50 outline-none"
+                />
+
+                {/* Webhook 列表 */}
+                <div className="mt-3 space-y-1">
+                  {reminders.webhooks.map((hook, i) => (
+                    <div key={i} className="flex items-center gap-2 text-[11px] text-gray-300">
+                      <input
+                        type="checkbox"
+                        checked={hook.enabled}
+                        onChange={(e) => updateHooks(reminders.webhooks.map((h, j) => (j === i ? { ...h, enabled: e.target.checked } : h)))}
+                        className="accent-blue-500"
+                      />
+                      <span className="flex-1 truncate" title={hook.url}>{hook.name || hook.kind} ({hook.kind})</span>
+                      {hookFailures[hook.url] && (
+                        <span className="text-red-400" title={hookFailures[hook.url].error}>
+                          推送失败 {new Date(hookFailures[hook.url].time).toLocaleString()}
+                        </span>
+                      )}
+                      <button onClick={() => handleTestHook(hook)} className="text-blue-400 hover:text-blue-300">测试</button>
+                      <button onClick={() => updateHooks(reminders.webhooks.filter((_, j) => j !== i))} className="text-red-400 hover:text-red-300">删除</button>
+                    </div>
+                  ))}
+                </div>
+                <div className="mt-2 grid grid-cols-2 gap-2">
+                  <input
+                    type="text"
+                    value={hookDraft.name}
+                    onChange={(e) => setHookDraft({ ...hookDraft, name: e.target.value })}
+                    placeholder="名称"
+                    className="bg-black/30 border border-white/10 rounded-lg px-2 py-1.5 text-[11px] text-gray-300 outline-none"
+                  />
+                  <select
+                    value={hookDraft.kind}
+                    onChange={(e) => setHookDraft({ ...hookDraft, kind: e.target.value as WebhookKind })}
+                    className="bg-black/30 border border-white/10 rounded-lg px-2 py-1.5 text-[11px] text-gray-300 outline-none"
+                  >
+                    <option value="generic">JSON</option>
+                    <option value="discord">Discord</option>
+                    <option value="slack">Slack</option>
+                    <option value="feishu">飞书 / Lark</option>
+                    <option value="dingtalk">钉钉</option>
+                    <option value="telegram">Telegram</option>
+                  </select>
+                  <input
+                    type="text"
+                    value={hookDraft.url}
+                    onChange={(e) => setHookDraft({ ...hookDraft, url: e.target.value })}
+                    placeholder="Webhook URL"
+                    className="col-span-2 bg-black/30 border border-white/10 rounded-lg px-2 py-1.5 text-[11px] text-gray-300 font-mono outline-none"
+                  />
+                  {hookDraft.kind === 'telegram' && (
+                    <input
+                      type="text"
+                      value={hookDraft.chat_id ?? ''}
+                      onChange={(e) => setHookDraft({ ...hookDraft, chat_id: e.target.value })}
+                      placeholder="chat_id"
+                      className="col-span-2 bg-black/30 border border-white/10 rounded-lg px-2 py-1.5 text-[11px] text-gray-300 font-mono outline-none"
+                    />
+                  )}
+                  <button
+                    onClick={() => {
+                      if (!hookDraft.url.trim()) return;
+                      updateHooks([...reminders.webhooks, hookDraft]);
+                      setHookDraft({ name: '', kind: 'generic', url: '', enabled: true });
+                    }}
+                    className="col-span-2 text-xs text-blue-400 hover:text-blue-300 underline decoration-blue-400/30"
+                  >
+                    添加 Webhook
+                  </button>
+                </div>
+                {hookStatus && <div className="mt-1 text-[11px] text-gray-400">{hookStatus}</div>}
+              </div>
+            )}
+
+            {refresh && (
+              <div className="mb-4">
+                <label className="block text-sm text-gray-300 mb-2 flex items-center justify-between">
+                  后台自动刷新
+                  <input
+                    type="checkbox"
+                    checked={refresh.enabled}
+                    onChange={(e) => setRefresh({ ...refresh, enabled: e.target.checked })}
+                    className="accent-blue-500"
+                  />
+                </label>
+                <div className="grid grid-cols-2 gap-2 mb-2">
+                  <input
+                    type="number"
+                    min={0}
+                    value={refresh.interval_minutes}
+                    onChange={(e) => setRefresh({ ...refresh, interval_minutes: Math.max(0, Number(e.target.value) || 0) })}
+                    title="比赛列表刷新间隔 (分钟)"
+                    className="bg-black/30 border border-white/10 rounded-lg px-3 py-2 text-[11px] text-gray-300 font-mono focus:border-blue-500/50 outline-none"
+                  />
+                  <input
+                    type="number"
+                    min={0}
+                    value={refresh.stats_interval_minutes}
+                    onChange={(e) => setRefresh({ ...refresh, stats_interval_minutes: Math.max(0, Number(e.target.value) || 0) })}
+                    title="战绩刷新间隔 (分钟)，0 为不刷新"
+                    className="bg-black/30 border border-white/10 rounded-lg px-3 py-2 text-[11px] text-gray-300 font-mono focus:border-blue-500/50 outline-none"
+                  />
+                </div>
+                <input
+                  type="text"
+                  value={intervalText}
+                  onChange={(e) => setIntervalText(e.target.value)}
+                  placeholder="按平台覆盖 (分钟), 如 Codeforces=10, clist=0"
+                  className="w-full bg-black/30 border border-white/10 rounded-lg px-3 py-2 text-[11px] text-gray-300 font-mono focus:border-blue-500/50 outline-none"
+                />
+              </div>
+            )}
+
             <div className="flex justify-end items-center gap-3">
               {cookieStatus && <span className="text-xs text-green-400 font-bold animate-pulse">{cookieStatus}</span>}
+              <button
+                onClick={handleValidateCookie}
+                className="text-xs text-blue-400 hover:text-blue-300 transition-colors underline decoration-blue-400/30"
+              >
+                检查登录态
+              </button>
               <button 
                 onClick={handleSaveCookie}
                 className="bg-blue-600 hover:bg-blue-500 text-white text-xs font-bold py-2 px-5 rounded-lg transition-all active:scale-95"
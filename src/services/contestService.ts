@@ -1,8 +1,17 @@
 import { invoke } from '@tauri-apps/api/core';
-import { Contest, UserStats } from '../types';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
+import { Contest, ContestChange, ContestFetchOptions, ContestsUpdate, LeetCodeDaily, NowCoderSession, RatingEvent, RefreshAccount, RefreshSettings, ReminderSettings, ScraperError, StatsUpdate, UserStats, WebhookConfig, WebhookFailure } from '../types';
 
-export const fetchAllContests = async (): Promise<Contest[]> => {
-  return await invoke('fetch_all_contests');
+export const fetchAllContests = async (options?: ContestFetchOptions): Promise<Contest[]> => {
+  // clist.by 账号保存在设置页，调用方未显式指定时自动带上
+  const merged: ContestFetchOptions = {
+    ...options,
+    clist: options?.clist ?? {
+      username: localStorage.getItem('clist_username'),
+      api_key: localStorage.getItem('clist_api_key'),
+    },
+  };
+  return await invoke('fetch_all_contests', { options: merged });
 };
 
 export const fetchUserStats = async (platform: string, handle: string): Promise<UserStats> => {
@@ -28,6 +37,17 @@ export const fetchUserStats = async (platform: string, handle: string): Promise<
     }
   }
 
+  // 洛谷 Cookie 为选填，配置后可读取本人隐藏的练习记录
+  if (platform.toLowerCase() === 'luogu') {
+    cookie = localStorage.getItem('luogu_cookie');
+  }
+
+  // LeetCode 账号所在站点 (cn / com)，未配置时后端默认按国服处理
+  let region: string | null = null;
+  if (platform.toLowerCase() === 'leetcode') {
+    region = localStorage.getItem('leetcode_region');
+  }
+
   // [Debug 3] 准备调用 Rust
   console.log(`[Frontend Debug] 正在调用 Tauri invoke('fetch_user_stats')...`);
   
@@ -35,7 +55,8 @@ export const fetchUserStats = async (platform: string, handle: string): Promise<
       const result = await invoke<UserStats>('fetch_user_stats', { 
         platform, 
         handle, 
-        cookie 
+        cookie,
+        region
       });
       console.log(`[Frontend Debug] ✅ Rust 返回成功:`, result);
       return result;
@@ -43,4 +64,80 @@ export const fetchUserStats = async (platform: string, handle: string): Promise<
       console.error(`[Frontend Debug] ❌ Rust 调用失败:`, e);
       throw e;
   }
-};
\ No newline at end of file
+};
+// Rating 历史；Cookie 与站点设置沿用 fetchUserStats 的配置项
+export const fetchRatingHistory = async (platform: string, handle: string): Promise<RatingEvent[]> => {
+  const key = platform.toLowerCase();
+  const cookie = key === 'nowcoder' || key === 'luogu' ? localStorage.getItem(`${key}_cookie`) : null;
+  const region = key === 'leetcode' ? localStorage.getItem('leetcode_region') : null;
+  return await invoke<RatingEvent[]>('fetch_rating_history', { platform, handle, cookie, region });
+};
+
+export const fetchLeetCodeDaily = async (): Promise<LeetCodeDaily> => {
+  const handle = localStorage.getItem('cpc_handle_leetcode');
+  const region = localStorage.getItem('leetcode_region');
+  return await invoke<LeetCodeDaily>('fetch_leetcode_daily', { handle, region });
+};
+
+export const validateNowCoderCookie = async (cookie: string): Promise<NowCoderSession> => {
+  return await invoke<NowCoderSession>('validate_nowcoder_cookie', { cookie });
+};
+
+export const getReminderSettings = async (): Promise<ReminderSettings> => {
+  return await invoke<ReminderSettings>('get_reminder_settings');
+};
+
+export const setReminderSettings = async (settings: ReminderSettings): Promise<void> => {
+  await invoke('set_reminder_settings', { settings });
+};
+
+export const testWebhook = async (hook: WebhookConfig): Promise<void> => {
+  await invoke('test_webhook', { hook });
+};
+
+export const getWebhookFailures = async (): Promise<WebhookFailure[]> => {
+  return await invoke<WebhookFailure[]>('get_webhook_failures');
+};
+
+export const getScraperErrors = async (): Promise<ScraperError[]> => {
+  return await invoke<ScraperError[]>('get_scraper_errors');
+};
+
+export const getRefreshSettings = async (): Promise<RefreshSettings> => {
+  return await invoke<RefreshSettings>('get_refresh_settings');
+};
+
+export const setRefreshSettings = async (settings: RefreshSettings): Promise<void> => {
+  await invoke('set_refresh_settings', { settings });
+};
+
+// 把已保存的 handle (及 Cookie / 站点设置) 同步给后台，用于定时刷新战绩
+export const syncRefreshAccounts = async (): Promise<void> => {
+  const accounts: RefreshAccount[] = [];
+  for (let i = 0; i < localStorage.length; i++) {
+    const key = localStorage.key(i);
+    if (!key?.startsWith('cpc_handle_')) continue;
+    const handle = localStorage.getItem(key)?.trim();
+    if (!handle) continue;
+    const platform = key.slice('cpc_handle_'.length);
+    const cookie = platform === 'nowcoder' || platform === 'luogu' ? localStorage.getItem(`${platform}_cookie`) : null;
+    const region = platform === 'leetcode' ? localStorage.getItem('leetcode_region') : null;
+    accounts.push({ platform, handle, cookie, region });
+  }
+  const settings = await getRefreshSettings();
+  await setRefreshSettings({ ...settings, accounts });
+};
+
+// 比赛变更记录 (改期 / 改名 / 取消)，最新的在前
+export const getContestChanges = async (): Promise<ContestChange[]> => {
+  return await invoke<ContestChange[]>('get_contest_changes');
+};
+
+// 后台刷新事件，返回取消监听的函数
+export const onContestsUpdated = (callback: (update: ContestsUpdate) => void): Promise<UnlistenFn> => {
+  return listen<ContestsUpdate>('contests-updated', (event) => callback(event.payload));
+};
+
+export const onStatsUpdated = (callback: (update: StatsUpdate) => void): Promise<UnlistenFn> => {
+  return listen<StatsUpdate>('stats-updated', (event) => callback(event.payload));
+};
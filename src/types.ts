@@ -3,6 +3,39 @@ export interface Contest {
   start_time: string; // ISO 8601 string
   url: string;
   platform: string;
+  end_time?: string | null; // ISO 8601 string
+  rated_range?: RatedRange | null;
+  format?: string | null;   // 赛制, e.g. OI / IOI / ACM
+  category?: string | null; // 比赛分类, e.g. 官方比赛
+  host?: string | null;
+  access?: string | null;   // 参赛权限, e.g. HDU Public / Private / Register
+}
+
+// fetch_all_contests 的可选参数
+export interface ContestFetchOptions {
+  luogu?: {
+    window_days?: number;
+    official_only?: boolean;
+    rated_only?: boolean;
+  };
+  hdu?: {
+    limit?: number | null;
+  };
+  nowcoder?: {
+    max_pages?: number;
+  };
+  // clist.by 聚合源，username 与 api_key 均填写才启用
+  clist?: {
+    username?: string | null;
+    api_key?: string | null;
+    window_days?: number;
+  };
+}
+
+// Rated 范围 (闭区间)，null 表示该侧不设限
+export interface RatedRange {
+  min: number | null;
+  max: number | null;
 }
 
 // [新增]
@@ -12,4 +45,228 @@ export interface UserStats {
   solved_count: number;
   rank?: string;
   rating?: number;
-}
\ No newline at end of file
+}
+// LeetCode 详细战绩 (fetch_leetcode_profile)
+export interface DifficultyBreakdown {
+  easy: number;
+  medium: number;
+  hard: number;
+  total: number;
+}
+
+export interface LeetCodeProfile {
+  handle: string;
+  solved: DifficultyBreakdown;
+  contest_ranking: {
+    rating: number;
+    attended_contests_count: number;
+    global_ranking: number | null;
+    top_percentage: number | null;
+  } | null;
+  rating_history: {
+    contest_name: string;
+    contest_slug: string;
+    start_time: string;
+    rating: number;
+    ranking: number;
+  }[];
+}
+
+// LeetCode 每日一题 (fetch_leetcode_daily)
+export interface DailyQuestion {
+  region: 'cn' | 'com';
+  date: string;
+  frontend_id: string;
+  title: string;
+  difficulty: string;
+  url: string;
+  solved: boolean | null;
+}
+
+export interface RecentAcSubmission {
+  title: string;
+  title_slug: string;
+  url: string;
+  time: string;
+}
+
+export interface LeetCodeDaily {
+  questions: DailyQuestion[];
+  recent_ac: RecentAcSubmission[];
+}
+
+// 洛谷详细战绩 (fetch_luogu_profile)
+export interface LuoguProfile {
+  uid: string;
+  name: string | null;
+  solved_count: number;
+  rating: number | null;
+  ranking: number | null;
+  passed_problems: string[] | null;
+  difficulty_distribution: LuoguDifficultyBucket[] | null;
+  authenticated: boolean;
+}
+
+export interface LuoguDifficultyBucket {
+  level: number;
+  name: string;
+  color: string;
+  count: number;
+  problems: string[];
+}
+
+// HDU 详细战绩 (fetch_hdu_profile)
+export interface HduProfile {
+  handle: string;
+  rank: number | null;
+  solved_count: number;
+  submitted_count: number;
+  submissions: number;
+  accepted: number;
+  solved_problems: number[];
+}
+
+// NowCoder Cookie 会话状态 (validate_nowcoder_cookie)
+export interface NowCoderSession {
+  logged_in: boolean;
+  uid: string | null;
+  expires_at: string | null;
+}
+
+// 代码源单场比赛 Rating 变化
+export interface DaimayuanRatingEntry {
+  contest_name: string;
+  contest_url: string | null;
+  rank: number | null;
+  old_rating: number | null;
+  new_rating: number;
+  time: string | null;
+}
+
+// 代码源详细战绩 (fetch_daimayuan_profile)
+export interface DaimayuanProfile {
+  handle: string;
+  rating: number | null;
+  solved_count: number;
+  rating_history: DaimayuanRatingEntry[];
+  solved_problems: string[];
+}
+
+// 统一格式的单场 Rating 变化 (fetch_rating_history)，按时间升序
+export interface RatingEvent {
+  platform: string;
+  contest_id: string;
+  contest_name: string;
+  time: string | null;
+  old_rating: number | null;
+  new_rating: number;
+  rank: number | null;
+  performance: number | null;
+}
+
+// CodeChef 详细战绩 (fetch_codechef_profile)
+export interface CodeChefProfile {
+  handle: string;
+  rating: number | null;
+  stars: number | null;
+  global_rank: number | null;
+  country_rank: number | null;
+  country: string | null;
+  solved_count: number;
+}
+
+// 比赛提醒设置 (get_reminder_settings / set_reminder_settings)
+export interface ReminderSettings {
+  enabled: boolean;
+  lead_minutes: number[];
+  platforms: string[];
+  contests: string[]; // 比赛链接
+  webhooks: WebhookConfig[];
+  daily_digest: string | null; // 每日赛程推送时间 "HH:MM"
+}
+
+export type WebhookKind = 'generic' | 'discord' | 'slack' | 'feishu' | 'dingtalk' | 'telegram';
+
+// 提醒推送的 Webhook；template 占位符: {platform} {name} {url} {start} {end} {remaining} {category} {format}
+export interface WebhookConfig {
+  name: string;
+  kind: WebhookKind;
+  url: string;
+  enabled: boolean;
+  chat_id?: string | null; // Telegram 必填
+  template?: string | null;
+}
+
+// 配置目录 scrapers/ 下无法加载的自定义爬虫定义
+export interface ScraperError {
+  file: string;
+  error: string;
+}
+
+// Webhook 最近一次自动推送失败的记录 (按 url 对应)
+export interface WebhookFailure {
+  name: string;
+  url: string;
+  error: string;
+  time: string;
+}
+
+// 后台自动刷新设置 (间隔单位为分钟，0 表示不自动刷新)
+export interface RefreshSettings {
+  enabled: boolean;
+  interval_minutes: number;
+  platform_intervals: Record<string, number>; // 键为平台名或 custom / clist
+  stats_interval_minutes: number;
+  accounts: RefreshAccount[];
+}
+
+export interface RefreshAccount {
+  platform: string; // 平台键, e.g. codeforces
+  handle: string;
+  cookie?: string | null;
+  region?: string | null;
+}
+
+// contests-updated 事件
+export interface ContestsUpdate {
+  contests: Contest[];
+  added: Contest[];
+  rescheduled: ContestReschedule[];
+  renamed: ContestRename[];
+  cancelled: Contest[];
+  changes: ContestChange[]; // 本次记录的变更事件
+}
+
+export interface ContestReschedule {
+  contest: Contest;
+  old_start_time: string;
+  old_end_time?: string | null;
+}
+
+export interface ContestRename {
+  contest: Contest;
+  old_name: string;
+}
+
+export type ContestChangeKind = 'rescheduled' | 'renamed' | 'cancelled';
+
+// 比赛变更记录 (get_contest_changes)；contest_id 即比赛链接
+export interface ContestChange {
+  contest_id: string;
+  platform: string;
+  name: string;
+  kind: ContestChangeKind;
+  detected_at: string;
+  start_time: string; // 变更后的开始时间 (取消时为原定时间)
+  old_start_time?: string | null;
+  shift_minutes?: number | null; // 正数为推迟
+  old_name?: string | null;
+}
+
+// stats-updated 事件
+export interface StatsUpdate {
+  platform: string;
+  handle: string;
+  stats: UserStats;
+  previous: UserStats | null;
+}
@@ -1,3 +1,5 @@
+import { RatedRange } from './types';
+
 // 平台颜色映射 - 采用“赤橙黄绿青蓝紫”光谱方案
 export const getPlatformColor = (platform: string): string => {
   switch (platform.toLowerCase()) {
@@ -15,6 +17,20 @@ export const getPlatformColor = (platform: string): string => {
       return '#3b82f6'; // 蓝 (Blue-500)
     case 'daimayuan':
       return '#8b5cf6'; // 紫 (Purple-500)
+    case 'codechef':
+      return '#a16207'; // 褐 (Yellow-700)
+    case 'yukicoder':
+      return '#14b8a6'; // 青绿 (Teal-500)
+    case 'topcoder':
+      return '#ec4899'; // 粉 (Pink-500)
+    case 'acwing':
+      return '#0ea5e9'; // 天蓝 (Sky-500)
+    case 'libreoj':
+      return '#84cc16'; // 黄绿 (Lime-500)
+    case 'uoj':
+      return '#6366f1'; // 靛 (Indigo-500)
+    case 'qoj':
+      return '#d946ef'; // 品红 (Fuchsia-500)
     default:
       return '#94a3b8'; // 默认 (Slate-400)
   }
@@ -98,4 +114,23 @@ export const hexToRgba = (hex: string, alpha: number): string => {
     return 'rgba(' + [(c >> 16) & 255, (c >> 8) & 255, c & 255].join(',') + ',' + alpha + ')';
   }
   return `rgba(31, 41, 55, ${alpha})`; 
-};
\ No newline at end of file
+};
+// 比赛改期的移动量, e.g. "推迟 2 小时" / "提前 30 分钟"
+export const formatShift = (minutes: number): string => {
+  const abs = Math.abs(minutes);
+  const days = Math.floor(abs / 1440);
+  const hours = Math.floor((abs % 1440) / 60);
+  const mins = abs % 60;
+  const parts = [days && `${days} 天`, hours && `${hours} 小时`, mins && `${mins} 分钟`].filter(Boolean);
+  return `${minutes >= 0 ? '推迟' : '提前'} ${parts.join(' ') || '0 分钟'}`;
+};
+
+// Rated 范围 (闭区间), e.g. "All" / "- 1999" / "1200 - 2799" / "1200 -"
+export const formatRatedRange = (range: RatedRange): string => {
+  if (range.min == null && range.max == null) return 'All';
+  return `${range.min ?? ''} - ${range.max ?? ''}`.trim();
+};
+
+// 该 Rating 是否计入此场比赛
+export const isRatedFor = (range: RatedRange, rating: number): boolean =>
+  (range.min == null || rating >= range.min) && (range.max == null || rating <= range.max);